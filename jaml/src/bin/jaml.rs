@@ -8,9 +8,13 @@ use std::{
 use anyhow::{Context, Result};
 use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use jaml::{
-    formatter::{BinaryEncoding, Options, QuoteStyle, TimestampPrecision, format_with_opts},
+    Value,
+    formatter::{
+        BinaryEncoding, Options, QuoteStyle, TimestampPrecision, format_with_opts, header_comment,
+    },
     parse,
 };
+use similar::TextDiff;
 
 /// JAML - Just Another Markup Language CLI tool
 #[derive(Parser)]
@@ -69,6 +73,26 @@ enum Commands {
         /// Check if file is already formatted (exit 1 if not)
         #[arg(long)]
         check_format: bool,
+
+        /// Don't print the message when --check-format finds a difference
+        #[arg(short, long, requires = "check_format")]
+        quiet: bool,
+
+        /// Print a unified diff of the changes instead of the output (exit 1 if there are any)
+        #[arg(long, conflicts_with = "output")]
+        diff: bool,
+
+        /// Print a top-level string without quotes or escapes (like `jq -r`)
+        #[arg(short, long, conflicts_with_all = ["check_format", "diff"])]
+        raw_output: bool,
+
+        /// Prepend TEXT as a comment header (one comment line per line of TEXT)
+        #[arg(long, value_name = "TEXT", conflicts_with = "raw_output")]
+        header: Option<String>,
+
+        /// Name to show in messages when the input is read from stdin
+        #[arg(long, value_name = "NAME")]
+        stdin_filename: Option<String>,
     },
 
     /// Check JAML syntax
@@ -85,6 +109,14 @@ enum Commands {
         /// Suppress success messages, only show errors
         #[arg(short, long)]
         quiet: bool,
+
+        /// Print the raw grammar parse tree (for debugging the grammar)
+        #[arg(long, hide = true)]
+        parse_tree: bool,
+
+        /// Name to show in messages when the input is read from stdin
+        #[arg(long, value_name = "NAME")]
+        stdin_filename: Option<String>,
     },
 
     /// Generate shell completions
@@ -164,6 +196,11 @@ fn main() {
             no_zulu,
             timestamp_precision,
             check_format,
+            quiet,
+            diff,
+            raw_output,
+            header,
+            stdin_filename,
         } => cmd_fmt(
             input,
             output,
@@ -176,12 +213,19 @@ fn main() {
             no_zulu,
             timestamp_precision,
             check_format,
+            quiet,
+            diff,
+            raw_output,
+            header.as_deref(),
+            stdin_filename.as_deref(),
         ),
         Commands::Check {
             files,
             verbose,
             quiet,
-        } => cmd_valid(files, verbose, quiet),
+            parse_tree,
+            stdin_filename,
+        } => cmd_valid(files, verbose, quiet, parse_tree, stdin_filename.as_deref()),
         Commands::Completions { shell } => {
             cmd_completions(shell);
             Ok(())
@@ -229,12 +273,19 @@ fn cmd_fmt(
     no_zulu: bool,
     timestamp_precision: TimestampPrecisionArg,
     check_format: bool,
+    quiet: bool,
+    diff: bool,
+    raw_output: bool,
+    header: Option<&str>,
+    stdin_filename: Option<&str>,
 ) -> Result<()> {
     // Read input
     let input_content = read_input(input.as_deref())?;
+    let name = display_name(input.as_deref(), stdin_filename);
 
     // Parse JAML
-    let value = parse(&input_content).context("Failed to parse JAML")?;
+    let value =
+        parse(&input_content).with_context(|| format!("Failed to parse JAML from {name}"))?;
 
     // Build formatting options
     let opts = build_format_options(
@@ -250,23 +301,40 @@ fn cmd_fmt(
 
     // Format
     let formatted = format_with_opts(&value, &opts);
+    let formatted = match header {
+        Some(header) => header_comment(header) + &formatted,
+        None => formatted,
+    };
 
-    // Check mode: compare and exit
-    if check_format {
-        check_formatting(&input_content, &formatted, input.as_deref());
+    // Check and diff modes: compare and exit
+    if check_format || diff {
+        if diff {
+            print_diff(&input_content, &formatted, name);
+        }
+        check_formatting(&input_content, &formatted, name, quiet || !check_format);
         return Ok(());
     }
 
-    // Write output
-    write_output(output.as_deref(), &formatted)?;
+    // Write output, unquoted for a top-level string in raw mode
+    match &value {
+        Value::String(s) if raw_output => write_output(output.as_deref(), s)?,
+        _ => write_output(output.as_deref(), &formatted)?,
+    }
 
     Ok(())
 }
 
-fn cmd_valid(files: Vec<PathBuf>, verbose: bool, quiet: bool) -> Result<()> {
+fn cmd_valid(
+    files: Vec<PathBuf>,
+    verbose: bool,
+    quiet: bool,
+    parse_tree: bool,
+    stdin_filename: Option<&str>,
+) -> Result<()> {
     if files.is_empty() {
         // Read from stdin
-        return validate_file(None, verbose, quiet);
+        let name = display_name(None, stdin_filename);
+        return validate_file(None, verbose, quiet, parse_tree).with_context(|| name.to_string());
     }
 
     let mut all_valid = true;
@@ -274,15 +342,19 @@ fn cmd_valid(files: Vec<PathBuf>, verbose: bool, quiet: bool) -> Result<()> {
 
     for file in &files {
         let file_path = parse_file_arg(file);
+        let shown = match (file_path, stdin_filename) {
+            (None, Some(name)) => name.to_string(),
+            _ => file.display().to_string(),
+        };
 
-        match validate_file(file_path, verbose, quiet) {
+        match validate_file(file_path, verbose, quiet, parse_tree) {
             Ok(()) => {
                 if !quiet {
-                    println!("✓ {}", file.display());
+                    println!("✓ {}", shown);
                 }
             }
             Err(e) => {
-                eprintln!("✗ {}: {:#}", file.display(), e);
+                eprintln!("✗ {}: {:#}", shown, e);
                 all_valid = false;
                 error_count += 1;
             }
@@ -299,8 +371,13 @@ fn cmd_valid(files: Vec<PathBuf>, verbose: bool, quiet: bool) -> Result<()> {
     Ok(())
 }
 
-fn validate_file(path: Option<&Path>, verbose: bool, quiet: bool) -> Result<()> {
+fn validate_file(path: Option<&Path>, verbose: bool, quiet: bool, parse_tree: bool) -> Result<()> {
     let content = read_input(path)?;
+
+    if parse_tree {
+        let tree = jaml::parse_tree(&content).context("Invalid JAML syntax")?;
+        print!("{}", tree);
+    }
     let value = parse(&content).context("Invalid JAML syntax")?;
 
     if verbose {
@@ -312,16 +389,36 @@ fn validate_file(path: Option<&Path>, verbose: bool, quiet: bool) -> Result<()>
     Ok(())
 }
 
-fn check_formatting(input: &str, formatted: &str, path: Option<&Path>) {
+/// Exits with status 1 if `input` differs from `formatted`; returns (status 0) if it matches.
+fn check_formatting(input: &str, formatted: &str, name: &str, quiet: bool) {
     if input.trim() != formatted.trim() {
-        let name = display_name(path);
-        eprintln!("File '{}' is not formatted correctly", name);
+        if !quiet {
+            eprintln!("File '{}' is not formatted correctly", name);
+        }
         process::exit(1);
     }
 }
 
-fn display_name(path: Option<&Path>) -> &str {
-    path.and_then(|p| p.to_str()).unwrap_or("stdin")
+/// Prints a unified diff from `input` to `formatted` (ending in one newline), unless
+/// [`check_formatting`] would accept `input`.
+fn print_diff(input: &str, formatted: &str, name: &str) {
+    if input.trim() == formatted.trim() {
+        return;
+    }
+    let formatted = format!("{}\n", formatted.trim_end_matches('\n'));
+    let diff = TextDiff::from_lines(input, &formatted);
+    print!(
+        "{}",
+        diff.unified_diff().header(&format!("{name}.orig"), name)
+    );
+}
+
+/// Name of an input for messages: its path, or `--stdin-filename` (default `stdin`) for stdin.
+fn display_name<'a>(path: Option<&'a Path>, stdin_filename: Option<&'a str>) -> &'a str {
+    match path {
+        Some(p) if p.to_str() != Some("-") => p.to_str().unwrap_or("stdin"),
+        _ => stdin_filename.unwrap_or("stdin"),
+    }
 }
 
 fn read_input(path: Option<&Path>) -> Result<String> {
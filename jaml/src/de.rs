@@ -20,6 +20,15 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Deserialize a JAML string into a Rust value.
+///
+/// A document with no value (empty, or only whitespace and comments) fails with
+/// [`ParseError::EmptyDocument`](crate::ParseError::EmptyDocument), even when `T` is an [`Option`]; it does not deserialize as
+/// `None`.
+///
+/// ```
+/// let err = jaml::from_str::<Option<i64>>("").unwrap_err();
+/// assert!(matches!(err, jaml::de::Error::ParseError(jaml::ParseError::EmptyDocument)));
+/// ```
 pub fn from_str<T>(s: &str) -> Result<T>
 where
     T: for<'de> Deserialize<'de>,
@@ -29,6 +38,8 @@ where
 }
 
 /// Deserialize a JAML [`Value`] into a Rust value.
+///
+/// Borrowed fields (`&str`, `#[serde(borrow)]` `Cow<str>`, ...) point into `value`.
 pub fn from_value<'de, T>(value: &'de Value) -> Result<T>
 where
     T: Deserialize<'de>,
@@ -21,14 +21,16 @@ use time::{format_description, macros::format_description as fd};
 
 use crate::{Binary, Value};
 
+mod error;
 /// Formatting options and configuration.
 mod options;
+pub use error::{Error, Result};
 pub use options::{BinaryEncoding, Options, QuoteStyle, TimestampPrecision};
 
 /// Formats a JAML [`Value`] into an indentation-based string.
 ///
 /// JAML format is inherently indentation-based (like YAML), so there's no compact vs pretty distinction.
-/// All output uses 2-space indentation by design.
+/// Output uses 2-space indentation; see [`Options::indent`] to change it.
 pub fn format(value: &Value) -> String {
     format_impl(value, &Options::default(), 0, false)
 }
@@ -38,6 +40,30 @@ pub fn format_with_opts(value: &Value, opts: &Options) -> String {
     format_impl(value, opts, 0, false)
 }
 
+/// Formats a JAML [`Value`] preceded by `header` as `#` comment lines.
+///
+/// Useful for generated files ("Generated by X, do not edit"). The parser skips comments, so
+/// the header does not affect the parsed value.
+///
+/// ```
+/// use jaml::{Value, formatter::{Options, to_string_with_header}};
+///
+/// let text = to_string_with_header(&Value::Int(1), "Generated file", &Options::default());
+/// assert_eq!(text, "# Generated file\n1");
+/// assert_eq!(jaml::parse(&text).unwrap(), Value::Int(1));
+/// ```
+pub fn to_string_with_header(value: &Value, header: &str, opts: &Options) -> String {
+    header_comment(header) + &format_with_opts(value, opts)
+}
+
+/// Renders `header` as `#` comment lines, ending with a newline.
+pub fn header_comment(header: &str) -> String {
+    header
+        .lines()
+        .map(|line| format!("{}\n", format!("# {line}").trim_end()))
+        .collect()
+}
+
 fn format_impl(value: &Value, opts: &Options, depth: usize, inline: bool) -> String {
     match value {
         Value::Null => "null".to_string(),
@@ -60,6 +86,7 @@ fn format_impl(value: &Value, opts: &Options, depth: usize, inline: bool) -> Str
         }
         Value::Binary(b) => format_binary(b, opts.binary_encoding),
         Value::Timestamp(t) => format_timestamp(t, opts),
+        Value::Duration(d) => format!("dur\"{}\"", jasn_core::duration::to_iso8601(d)),
         Value::List(items) => format_list(items, opts, depth, inline),
         Value::Map(map) => format_map(map, opts, depth, inline),
     }
@@ -86,7 +113,14 @@ fn format_float(f: f64, opts: &Options) -> String {
         // Ensure we always have a decimal point to distinguish from integers
         format!("{:.1}", f)
     } else {
-        f.to_string()
+        // Whole floats from 1e15 up display without a decimal point, which would read back
+        // as an integer, so those use exponent notation
+        let s = f.to_string();
+        if s.contains('.') {
+            s
+        } else {
+            format!("{:e}", f)
+        }
     };
 
     // Add leading plus for positive numbers (including +inf, but not nan)
@@ -168,7 +202,8 @@ fn format_string(s: &str, quote: char, escape_unicode: bool) -> String {
             '\r' => result.push_str("\\r"),
             '\x08' => result.push_str("\\b"),
             '\x0C' => result.push_str("\\f"),
-            c if c.is_control() => {
+            // U+2028/U+2029 end a line in JavaScript, so they are escaped like control characters
+            c if c.is_control() || matches!(c, '\u{2028}' | '\u{2029}') => {
                 use std::fmt::Write;
                 write!(&mut result, "\\u{:04x}", c as u32).unwrap();
             }
@@ -214,7 +249,7 @@ fn format_list(items: &[Value], opts: &Options, depth: usize, inline: bool) -> S
         return "[]".to_string();
     }
 
-    let indent = "  ".repeat(depth);
+    let indent = opts.indent.repeat(depth);
     let mut result = String::new();
 
     for (i, item) in items.iter().enumerate() {
@@ -252,7 +287,7 @@ fn format_map(map: &BTreeMap<String, Value>, opts: &Options, depth: usize, inlin
         return "{}".to_string();
     }
 
-    let indent = "  ".repeat(depth);
+    let indent = opts.indent.repeat(depth);
     let mut result = String::new();
 
     let entries: Vec<_> = if opts.sort_keys {
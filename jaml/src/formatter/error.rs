@@ -0,0 +1,13 @@
+/// Errors that can occur during formatting.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// [`Options::indent`] is empty or not made up solely of spaces or solely of tabs.
+    ///
+    /// [`Options::indent`]: super::Options::indent
+    #[error("Invalid indent {0:?} (must be one or more spaces or one or more tabs)")]
+    InvalidIndent(String),
+}
+
+/// Result type for formatting operations.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -1,6 +1,12 @@
+use super::{Error, Result};
+
 /// Formatting options for JAML output.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Options {
+    /// Indentation for one nesting level. Must be non-empty and made up solely of spaces or
+    /// solely of tabs, since JAML structure is defined by indentation; see [`Self::validate`].
+    pub indent: String,
+
     /// Quote style for strings.
     pub quote_style: QuoteStyle,
 
@@ -29,6 +35,7 @@ pub struct Options {
 impl Default for Options {
     fn default() -> Self {
         Self {
+            indent: "  ".to_string(),
             quote_style: QuoteStyle::Double,
             binary_encoding: BinaryEncoding::Base64,
             unquoted_keys: true,
@@ -47,6 +54,35 @@ impl Options {
         Self::default()
     }
 
+    /// Sets the indentation for one nesting level (such as `"    "` or `"\t"`).
+    ///
+    /// The string is not checked here; use [`Self::validate`] to reject indentation that would
+    /// not parse back.
+    pub fn with_indent(mut self, indent: impl Into<String>) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
+    /// Checks that the options describe parseable output.
+    ///
+    /// Currently this rejects an [`indent`](Self::indent) that is empty, mixes spaces and tabs,
+    /// or contains any other character.
+    ///
+    /// ```
+    /// use jaml::formatter::Options;
+    ///
+    /// assert!(Options::new().with_indent("\t").validate().is_ok());
+    /// assert!(Options::new().with_indent(" \t").validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        let indent = &self.indent;
+        let uniform = indent.chars().all(|c| c == ' ') || indent.chars().all(|c| c == '\t');
+        if indent.is_empty() || !uniform {
+            return Err(Error::InvalidIndent(indent.clone()));
+        }
+        Ok(())
+    }
+
     /// Sets the quote style.
     pub fn with_quote_style(mut self, style: QuoteStyle) -> Self {
         self.quote_style = style;
@@ -145,6 +181,7 @@ mod tests {
     #[test]
     fn test_default_options() {
         let opts = Options::default();
+        assert_eq!(opts.indent, "  ");
         assert_eq!(opts.quote_style, QuoteStyle::Double);
         assert_eq!(opts.binary_encoding, BinaryEncoding::Base64);
         assert!(opts.unquoted_keys);
@@ -168,4 +205,45 @@ mod tests {
         assert!(!opts.unquoted_keys);
         assert!(!opts.sort_keys);
     }
+
+    #[test]
+    fn test_validate_indent() {
+        assert!(Options::default().validate().is_ok());
+        assert!(Options::new().with_indent("    ").validate().is_ok());
+        assert!(Options::new().with_indent("\t\t").validate().is_ok());
+        assert!(matches!(
+            Options::new().with_indent(" \t").validate(),
+            Err(Error::InvalidIndent(s)) if s == " \t"
+        ));
+        assert!(Options::new().with_indent("").validate().is_err());
+        assert!(Options::new().with_indent("x").validate().is_err());
+    }
+
+    #[test]
+    fn test_builders_cover_every_field() {
+        let opts = Options::new()
+            .with_indent("\t")
+            .with_quote_style(QuoteStyle::PreferDouble)
+            .with_binary_encoding(BinaryEncoding::Hex)
+            .with_unquoted_keys(false)
+            .with_leading_plus(true)
+            .with_sort_keys(false)
+            .with_escape_unicode(true)
+            .with_use_zulu(false)
+            .with_timestamp_precision(TimestampPrecision::Milliseconds);
+
+        // A struct literal, so a new field fails to compile here until it gets a builder
+        let expected = Options {
+            indent: "\t".to_string(),
+            quote_style: QuoteStyle::PreferDouble,
+            binary_encoding: BinaryEncoding::Hex,
+            unquoted_keys: false,
+            leading_plus: true,
+            sort_keys: false,
+            escape_unicode: true,
+            use_zulu: false,
+            timestamp_precision: TimestampPrecision::Milliseconds,
+        };
+        assert_eq!(opts, expected);
+    }
 }
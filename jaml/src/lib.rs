@@ -60,13 +60,15 @@
 #![warn(missing_docs)]
 
 // Re-export core types
-pub use jasn_core::{Binary, Timestamp, Value};
+pub use jasn_core::{Binary, Timestamp, Value, timestamp};
 
 pub mod formatter;
 mod parser;
 
 pub use formatter::{format, format_with_opts};
-pub use parser::{Error as ParseError, Result as ParseResult, parse};
+pub use parser::{
+    Error as ParseError, ParseOptions, Result as ParseResult, parse, parse_tree, parse_with_opts,
+};
 
 #[cfg(feature = "serde")]
 pub mod de;
@@ -76,4 +78,4 @@ pub mod ser;
 #[cfg(feature = "serde")]
 pub use de::{from_str, from_value};
 #[cfg(feature = "serde")]
-pub use ser::{to_string, to_string_pretty, to_value};
+pub use ser::{to_string, to_string_opts, to_string_pretty, to_value};
@@ -16,11 +16,38 @@ use crate::Value;
 
 mod error;
 mod indent;
+mod options;
 mod parse;
 
 pub use error::{Error, Result};
+pub use options::ParseOptions;
 
 /// Parse a JAML string into a [`Value`].
 pub fn parse(input: &str) -> Result<Value> {
-    parse::parse_impl(input)
+    parse::parse_impl(input, &ParseOptions::default())
+}
+
+/// Parse a JAML string into a [`Value`], accepting the extensions enabled in `opts`.
+///
+/// ```
+/// use jaml::{ParseOptions, parse_with_opts};
+///
+/// let opts = ParseOptions::new().with_bare_key_is_null(true);
+/// let value = parse_with_opts("a:\nb: 1", &opts).unwrap();
+/// assert_eq!(value.get("a"), Some(&jaml::Value::Null));
+/// ```
+pub fn parse_with_opts(input: &str, opts: &ParseOptions) -> Result<Value> {
+    parse::parse_impl(input, opts)
+}
+
+/// Render the raw grammar parse tree of a JAML string, for debugging the grammar.
+///
+/// Only the syntax stage runs, so indentation and value errors are not reported.
+///
+/// ```
+/// let tree = jaml::parse_tree("a: 1").unwrap();
+/// assert!(tree.contains("map_entry 1:1 0..4 \"a: 1\""));
+/// ```
+pub fn parse_tree(input: &str) -> Result<String> {
+    parse::parse_tree_impl(input)
 }
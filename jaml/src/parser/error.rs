@@ -44,29 +44,57 @@ pub enum Error {
     #[error("Invalid timestamp '{0}': {1}")]
     InvalidTimestamp(String, String),
 
-    /// Mixed tabs and spaces in indentation base unit.
-    #[error("Mixed tabs and spaces in indentation, got '{0:?}'")]
-    MixedIndent(String),
+    /// Invalid ISO 8601 duration.
+    #[error("Invalid duration '{0}': {1}")]
+    InvalidDuration(String, String),
 
-    /// Inconsistent indentation type (switching between spaces and tabs).
-    #[error("Inconsistent indent char: expected '{0}', got '{1}'")]
-    InconsistentIndentTab(indent::Tab, indent::Tab),
+    /// Mixed tabs and spaces in indentation at the given line.
+    #[error("Mixed tabs and spaces in indentation at line {0}, got '{1:?}'")]
+    MixedIndent(usize, String),
 
-    /// Invalid indentation (not a multiple of the base unit).
-    #[error("Invalid indentation: expected multiple of {0}, got {1}")]
-    InvalidIndentCount(usize, usize),
+    /// Inconsistent indentation type (switching between spaces and tabs) at the given line.
+    #[error("Inconsistent indent char at line {0}: expected '{1}', got '{2}'")]
+    InconsistentIndentTab(usize, indent::Tab, indent::Tab),
 
-    /// Unexpected indentation level.
-    #[error("Unexpected indentation: expected {0}, got {1}")]
-    UnexpectedIndent(usize, usize),
+    /// Invalid indentation (not a multiple of the base unit) at the given line.
+    #[error("Invalid indentation at line {0}: expected multiple of {1}, got {2}")]
+    InvalidIndentCount(usize, usize, usize),
+
+    /// Unexpected indentation level at the given line.
+    #[error("Unexpected indentation at line {0}: expected {1}, got {2}")]
+    UnexpectedIndent(usize, usize, usize),
 
     /// Empty document.
     #[error("Empty document")]
     EmptyDocument,
 
-    /// Missing value for list item or map entry.
+    /// Missing value for list item or map entry at the given line.
     #[error("Missing value at line {0}")]
     MissingValue(usize),
+
+    /// Content after the root value ends, starting at the given line.
+    #[error("Unexpected content after the document at line {0}")]
+    TrailingContent(usize),
+
+    /// Alias refers to an anchor that has not been defined.
+    #[error("Undefined alias: *{0}")]
+    UndefinedAlias(String),
+
+    /// Alias refers to the anchored value that contains it.
+    #[error("Cyclic alias: *{0} refers to a value that contains it")]
+    CyclicAlias(String),
+
+    /// Aliases copied more values than [`ParseOptions::max_alias_nodes`] allows.
+    ///
+    /// [`ParseOptions::max_alias_nodes`]: super::ParseOptions::max_alias_nodes
+    #[error("Aliases expand to more than {0} values")]
+    AliasLimitExceeded(usize),
+
+    /// The parse tree did not have the shape the grammar guarantees.
+    ///
+    /// This indicates a bug in the parser rather than in the input.
+    #[error("Internal parser error: {0}")]
+    Internal(String),
 }
 
 /// Result type for parsing operations.
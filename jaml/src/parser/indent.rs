@@ -65,15 +65,15 @@ pub struct Tracker {
 
 impl Tracker {
     /// Validate and track indentation for a line
-    /// Returns the indent level (0, 1, 2, ...) if valid
-    pub fn validate(&mut self, indent_str: &str) -> Result<usize> {
+    /// Returns the indent level (0, 1, 2, ...) if valid; `line_num` is only used for errors
+    pub fn validate(&mut self, indent_str: &str, line_num: usize) -> Result<usize> {
         if indent_str.is_empty() {
             return Ok(0);
         }
 
         // Check if it mixes spaces and tabs
-        let tab =
-            Tab::from_str(indent_str).ok_or_else(|| Error::MixedIndent(indent_str.to_string()))?;
+        let tab = Tab::from_str(indent_str)
+            .ok_or_else(|| Error::MixedIndent(line_num, indent_str.to_string()))?;
 
         match self.style {
             None => {
@@ -85,12 +85,12 @@ impl Tracker {
             Some(style) => {
                 // Check consistency
                 if tab != style.tab {
-                    return Err(Error::InconsistentIndentTab(style.tab, tab));
+                    return Err(Error::InconsistentIndentTab(line_num, style.tab, tab));
                 }
 
                 let count = indent_str.len();
                 if !count.is_multiple_of(style.count) {
-                    return Err(Error::InvalidIndentCount(style.count, count));
+                    return Err(Error::InvalidIndentCount(line_num, style.count, count));
                 }
 
                 Ok(count / style.count)
@@ -0,0 +1,50 @@
+/// Options controlling which extensions the JAML parser accepts.
+///
+/// The default accepts exactly the JAML grammar, with [`max_alias_nodes`](Self::max_alias_nodes)
+/// guarding against alias bombs; the other options opt into relaxations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Parse a map entry with nothing after the colon and nothing indented below it
+    /// (`key:`) as `key: null`, as YAML does, instead of reporting
+    /// [`Error::MissingValue`](super::Error::MissingValue).
+    pub bare_key_is_null: bool,
+
+    /// Maximum total number of values that aliases may copy into the document, or `None` for
+    /// no limit. Defaults to [`Self::DEFAULT_MAX_ALIAS_NODES`].
+    ///
+    /// Each `*alias` copies its anchored value, so nested aliases can expand a short input
+    /// exponentially ("billion laughs"). Exceeding the limit fails with
+    /// [`Error::AliasLimitExceeded`](super::Error::AliasLimitExceeded).
+    pub max_alias_nodes: Option<usize>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            bare_key_is_null: false,
+            max_alias_nodes: Some(Self::DEFAULT_MAX_ALIAS_NODES),
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Default for [`Self::max_alias_nodes`].
+    pub const DEFAULT_MAX_ALIAS_NODES: usize = 1_000_000;
+
+    /// Creates options that accept only standard JAML.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the limit on values copied in by aliases (`None` disables it).
+    pub fn with_max_alias_nodes(mut self, limit: Option<usize>) -> Self {
+        self.max_alias_nodes = limit;
+        self
+    }
+
+    /// Sets whether a map entry without a value parses as `null`.
+    pub fn with_bare_key_is_null(mut self, enable: bool) -> Self {
+        self.bare_key_is_null = enable;
+        self
+    }
+}
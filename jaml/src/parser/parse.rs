@@ -2,12 +2,18 @@
 
 #![allow(missing_docs)]
 
-use std::{collections::BTreeMap, result::Result as StdResult};
-
-use pest::{Parser, iterators::Pair};
+use std::{
+    collections::{BTreeMap, HashMap},
+    result::Result as StdResult,
+};
+
+use pest::{
+    Parser,
+    iterators::{Pair, Pairs},
+};
 use pest_derive::Parser;
 
-use super::{Error, Result, indent};
+use super::{Error, ParseOptions, Result, indent};
 use crate::{Binary, Value};
 
 pub(super) type PestError = pest::error::Error<Rule>;
@@ -18,21 +24,107 @@ pub(super) type PestError = pest::error::Error<Rule>;
 pub(super) struct JamlParser;
 
 #[derive(Debug, Clone)]
-struct Line {
+struct Line<'i> {
     indent: usize,
-    content: LineContent,
+    content: LineContent<'i>,
     line_num: usize,
 }
 
 #[derive(Debug, Clone)]
-enum LineContent {
-    ListItem(Option<Value>),
-    MapEntry(String, Option<Value>),
-    Value(Value),
+enum LineContent<'i> {
+    ListItem(Item<'i>),
+    MapEntry(String, Item<'i>),
+    Value(Pair<'i, Rule>),
     Empty,
 }
 
-pub(super) fn parse_impl(input: &str) -> Result<Value> {
+/// The part of a list item or map entry after `-` or `key:`.
+///
+/// Inline values are kept as unparsed pairs so that aliases can be resolved in document order
+/// while the tree is built.
+#[derive(Debug, Clone)]
+struct Item<'i> {
+    anchor: Option<String>,
+    value: Option<Pair<'i, Rule>>,
+}
+
+/// Anchors defined so far while building the document.
+#[derive(Debug)]
+struct Anchors {
+    /// Each anchored value with its number of nodes.
+    defined: HashMap<String, (Value, usize)>,
+    /// Anchors whose value is still being built; referencing one of these is a cycle.
+    pending: Vec<String>,
+    /// Nodes copied by aliases so far, checked against `limit`.
+    expanded: usize,
+    limit: Option<usize>,
+}
+
+impl Anchors {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            defined: HashMap::new(),
+            pending: Vec::new(),
+            expanded: 0,
+            limit,
+        }
+    }
+
+    fn define(&mut self, name: String, value: Value) {
+        let nodes = value.count_by_type().total();
+        self.defined.insert(name, (value, nodes));
+    }
+
+    fn resolve(&mut self, name: &str) -> Result<Value> {
+        if self.pending.iter().any(|pending| pending == name) {
+            return Err(Error::CyclicAlias(name.to_string()));
+        }
+        let (value, nodes) = self
+            .defined
+            .get(name)
+            .ok_or_else(|| Error::UndefinedAlias(name.to_string()))?;
+        // Checked before cloning, so an oversized expansion is never built
+        self.expanded = self.expanded.saturating_add(*nodes);
+        if let Some(limit) = self.limit
+            && self.expanded > limit
+        {
+            return Err(Error::AliasLimitExceeded(limit));
+        }
+        Ok(value.clone())
+    }
+}
+
+/// Renders the raw pest parse tree of `input`, one pair per line, indented by depth.
+///
+/// Each line shows the rule name, the `line:column` and byte range of its span, and its text.
+pub(super) fn parse_tree_impl(input: &str) -> Result<String> {
+    let mut out = String::new();
+    for pair in JamlParser::parse(Rule::jaml, input)? {
+        write_tree(&mut out, pair, 0);
+    }
+    Ok(out)
+}
+
+fn write_tree(out: &mut String, pair: Pair<Rule>, depth: usize) {
+    let span = pair.as_span();
+    let (line, column) = span.start_pos().line_col();
+    out.push_str(&format!(
+        "{:indent$}{:?} {}:{} {}..{} {:?}\n",
+        "",
+        pair.as_rule(),
+        line,
+        column,
+        span.start(),
+        span.end(),
+        span.as_str(),
+        indent = depth * 2,
+    ));
+    for inner in pair.into_inner() {
+        write_tree(out, inner, depth + 1);
+    }
+}
+
+pub(super) fn parse_impl(input: &str, opts: &ParseOptions) -> Result<Value> {
     let pairs = JamlParser::parse(Rule::jaml, input)?;
 
     // Parse all lines
@@ -43,33 +135,37 @@ pub(super) fn parse_impl(input: &str) -> Result<Value> {
     }
 
     // Build value from lines
-    let (value, _) = build_value(&lines, 0, 0)?;
+    let mut anchors = Anchors::new(opts.max_alias_nodes);
+    let (value, next_idx) = build_value(&lines, 0, 0, opts, &mut anchors)?;
+    if let Some(line) = lines.get(next_idx) {
+        return Err(Error::TrailingContent(line.line_num));
+    }
     Ok(value)
 }
 
-fn parse_lines(pairs: pest::iterators::Pairs<Rule>) -> Result<Vec<Line>> {
+fn parse_lines(pairs: Pairs<Rule>) -> Result<Vec<Line>> {
     let mut lines = Vec::new();
     let mut line_num = 1;
     let mut indent_tracker = indent::Tracker::default();
 
     for pair in pairs {
         if pair.as_rule() == Rule::jaml {
-            let document = pair.into_inner().next().unwrap();
+            let document = first_inner(pair)?;
             for line_pair in document.into_inner() {
                 match line_pair.as_rule() {
                     Rule::non_empty_line => {
                         let mut inner = line_pair.into_inner();
 
                         // Get indent
-                        let indent_pair = inner.next().unwrap();
+                        let indent_pair = next_pair(&mut inner, Rule::non_empty_line)?;
                         let indent_str = indent_pair.as_str();
 
                         // Validate and get indent level using tracker
-                        let indent = indent_tracker.validate(indent_str)?;
+                        let indent = indent_tracker.validate(indent_str, line_num)?;
 
                         // Get content
-                        let content_pair = inner.next().unwrap();
-                        let content = parse_line_content(content_pair, line_num)?;
+                        let content_pair = next_pair(&mut inner, Rule::non_empty_line)?;
+                        let content = parse_line_content(content_pair)?;
 
                         if !matches!(content, LineContent::Empty) {
                             lines.push(Line {
@@ -94,47 +190,48 @@ fn parse_lines(pairs: pest::iterators::Pairs<Rule>) -> Result<Vec<Line>> {
     Ok(lines)
 }
 
-fn parse_line_content(pair: Pair<Rule>, _line_num: usize) -> Result<LineContent> {
+fn parse_line_content(pair: Pair<Rule>) -> Result<LineContent> {
     match pair.as_rule() {
         Rule::content => {
-            let inner = pair.into_inner().next().unwrap();
-            parse_line_content(inner, _line_num)
-        }
-        Rule::list_item => {
-            let value = pair
-                .into_inner()
-                .find_map(|inner| {
-                    match inner.as_rule() {
-                        Rule::inline_value => Some(parse_inline_value(inner)),
-                        _ => None, // Skip trailing_ws and comment
-                    }
-                })
-                .transpose()?;
-            Ok(LineContent::ListItem(value))
+            let inner = first_inner(pair)?;
+            parse_line_content(inner)
         }
+        Rule::list_item => Ok(LineContent::ListItem(parse_item(pair.into_inner()))),
         Rule::map_entry => {
             let mut inner = pair.into_inner();
-            let key = parse_key(inner.next().unwrap())?;
-            let value = inner
-                .find_map(|pair| {
-                    match pair.as_rule() {
-                        Rule::inline_value => Some(parse_inline_value(pair)),
-                        _ => None, // Skip trailing_ws and comment
-                    }
-                })
-                .transpose()?;
-            Ok(LineContent::MapEntry(key, value))
-        }
-        Rule::inline_value => {
-            let value = parse_inline_value(pair)?;
-            Ok(LineContent::Value(value))
+            let key = parse_key(next_pair(&mut inner, Rule::map_entry)?)?;
+            Ok(LineContent::MapEntry(key, parse_item(inner)))
         }
+        Rule::inline_value => Ok(LineContent::Value(pair)),
         Rule::comment => Ok(LineContent::Empty),
         _ => Ok(LineContent::Empty),
     }
 }
 
-fn build_value(lines: &[Line], start_idx: usize, expected_indent: usize) -> Result<(Value, usize)> {
+fn parse_item<'i>(pairs: Pairs<'i, Rule>) -> Item<'i> {
+    let mut item = Item {
+        anchor: None,
+        value: None,
+    };
+
+    for pair in pairs {
+        match pair.as_rule() {
+            Rule::anchor => item.anchor = Some(pair.as_str()[1..].to_string()),
+            Rule::inline_value => item.value = Some(pair),
+            _ => {} // Skip trailing_ws and comment
+        }
+    }
+
+    item
+}
+
+fn build_value(
+    lines: &[Line],
+    start_idx: usize,
+    expected_indent: usize,
+    opts: &ParseOptions,
+    anchors: &mut Anchors,
+) -> Result<(Value, usize)> {
     if start_idx >= lines.len() {
         return Err(Error::EmptyDocument);
     }
@@ -142,18 +239,69 @@ fn build_value(lines: &[Line], start_idx: usize, expected_indent: usize) -> Resu
     let first = &lines[start_idx];
 
     if first.indent != expected_indent {
-        return Err(Error::UnexpectedIndent(expected_indent, first.indent));
+        return Err(Error::UnexpectedIndent(
+            first.line_num,
+            expected_indent,
+            first.indent,
+        ));
     }
 
     match &first.content {
-        LineContent::Value(v) => Ok((v.clone(), start_idx + 1)),
-        LineContent::ListItem(_) => build_list(lines, start_idx, expected_indent),
-        LineContent::MapEntry(_, _) => build_map(lines, start_idx, expected_indent),
+        LineContent::Value(pair) => Ok((parse_inline_value(pair.clone(), anchors)?, start_idx + 1)),
+        LineContent::ListItem(_) => build_list(lines, start_idx, expected_indent, opts, anchors),
+        LineContent::MapEntry(_, _) => build_map(lines, start_idx, expected_indent, opts, anchors),
         LineContent::Empty => Err(Error::EmptyDocument),
     }
 }
 
-fn build_list(lines: &[Line], start_idx: usize, expected_indent: usize) -> Result<(Value, usize)> {
+/// Builds the value of the list item or map entry at `idx`, returning it with the index of
+/// the next unconsumed line.
+///
+/// An item with no value, inline or indented below, is `null` if `missing_is_null` is set and
+/// an error otherwise.
+fn build_item(
+    lines: &[Line],
+    idx: usize,
+    item: &Item,
+    missing_is_null: bool,
+    opts: &ParseOptions,
+    anchors: &mut Anchors,
+) -> Result<(Value, usize)> {
+    let line = &lines[idx];
+
+    if let Some(name) = &item.anchor {
+        anchors.pending.push(name.clone());
+    }
+
+    let (value, next_idx) = match &item.value {
+        Some(pair) => (parse_inline_value(pair.clone(), anchors)?, idx + 1),
+        // Value on next line, which must be indented further
+        None if lines
+            .get(idx + 1)
+            .is_some_and(|next| next.indent > line.indent) =>
+        {
+            build_value(lines, idx + 1, line.indent + 1, opts, anchors)?
+        }
+        None if missing_is_null => (Value::Null, idx + 1),
+        None => return Err(Error::MissingValue(line.line_num)),
+    };
+
+    if let Some(name) = &item.anchor {
+        // Anchors nest, so the innermost pending one finishes first
+        anchors.pending.pop();
+        anchors.define(name.clone(), value.clone());
+    }
+
+    Ok((value, next_idx))
+}
+
+fn build_list(
+    lines: &[Line],
+    start_idx: usize,
+    expected_indent: usize,
+    opts: &ParseOptions,
+    anchors: &mut Anchors,
+) -> Result<(Value, usize)> {
     let mut items = Vec::new();
     let mut idx = start_idx;
 
@@ -165,25 +313,18 @@ fn build_list(lines: &[Line], start_idx: usize, expected_indent: usize) -> Resul
         }
 
         if line.indent > expected_indent {
-            return Err(Error::UnexpectedIndent(expected_indent, line.indent));
+            return Err(Error::UnexpectedIndent(
+                line.line_num,
+                expected_indent,
+                line.indent,
+            ));
         }
 
         match &line.content {
-            LineContent::ListItem(maybe_val) => {
-                if let Some(val) = maybe_val {
-                    items.push(val.clone());
-                    idx += 1;
-                } else {
-                    // Value on next line
-                    idx += 1;
-                    if idx < lines.len() {
-                        let (nested_val, next_idx) = build_value(lines, idx, expected_indent + 1)?;
-                        items.push(nested_val);
-                        idx = next_idx;
-                    } else {
-                        return Err(Error::MissingValue(line.line_num));
-                    }
-                }
+            LineContent::ListItem(item) => {
+                let (value, next_idx) = build_item(lines, idx, item, false, opts, anchors)?;
+                items.push(value);
+                idx = next_idx;
             }
             _ => break,
         }
@@ -192,7 +333,13 @@ fn build_list(lines: &[Line], start_idx: usize, expected_indent: usize) -> Resul
     Ok((Value::List(items), idx))
 }
 
-fn build_map(lines: &[Line], start_idx: usize, expected_indent: usize) -> Result<(Value, usize)> {
+fn build_map(
+    lines: &[Line],
+    start_idx: usize,
+    expected_indent: usize,
+    opts: &ParseOptions,
+    anchors: &mut Anchors,
+) -> Result<(Value, usize)> {
     let mut map = BTreeMap::new();
     let mut idx = start_idx;
 
@@ -204,29 +351,23 @@ fn build_map(lines: &[Line], start_idx: usize, expected_indent: usize) -> Result
         }
 
         if line.indent > expected_indent {
-            return Err(Error::UnexpectedIndent(expected_indent, line.indent));
+            return Err(Error::UnexpectedIndent(
+                line.line_num,
+                expected_indent,
+                line.indent,
+            ));
         }
 
         match &line.content {
-            LineContent::MapEntry(key, maybe_val) => {
+            LineContent::MapEntry(key, item) => {
                 if map.contains_key(key) {
                     return Err(Error::DuplicateKey(key.clone()));
                 }
 
-                if let Some(val) = maybe_val {
-                    map.insert(key.clone(), val.clone());
-                    idx += 1;
-                } else {
-                    // Value on next line
-                    idx += 1;
-                    if idx < lines.len() {
-                        let (nested_val, next_idx) = build_value(lines, idx, expected_indent + 1)?;
-                        map.insert(key.clone(), nested_val);
-                        idx = next_idx;
-                    } else {
-                        return Err(Error::MissingValue(line.line_num));
-                    }
-                }
+                let (value, next_idx) =
+                    build_item(lines, idx, item, opts.bare_key_is_null, opts, anchors)?;
+                map.insert(key.clone(), value);
+                idx = next_idx;
             }
             _ => break,
         }
@@ -238,24 +379,24 @@ fn build_map(lines: &[Line], start_idx: usize, expected_indent: usize) -> Result
 fn parse_key(pair: Pair<Rule>) -> Result<String> {
     match pair.as_rule() {
         Rule::key => {
-            let inner = pair.into_inner().next().unwrap();
+            let inner = first_inner(pair)?;
             parse_key(inner)
         }
         Rule::identifier => Ok(pair.as_str().to_string()),
         Rule::string => {
-            if let Value::String(s) = parse_string(pair)? {
-                Ok(s)
-            } else {
-                unreachable!()
+            let rule = pair.as_rule();
+            match parse_string(pair)? {
+                Value::String(s) => Ok(s),
+                _ => Err(unexpected_rule(rule)),
             }
         }
-        _ => unreachable!("Unexpected key rule: {:?}", pair.as_rule()),
+        other => Err(unexpected_rule(other)),
     }
 }
 
-fn parse_inline_value(pair: Pair<Rule>) -> Result<Value> {
+fn parse_inline_value(pair: Pair<Rule>, anchors: &mut Anchors) -> Result<Value> {
     let rule = if pair.as_rule() == Rule::inline_value {
-        pair.into_inner().next().unwrap()
+        first_inner(pair)?
     } else {
         pair
     };
@@ -268,32 +409,34 @@ fn parse_inline_value(pair: Pair<Rule>) -> Result<Value> {
         Rule::string => parse_string(rule),
         Rule::binary => parse_binary(rule),
         Rule::timestamp => parse_timestamp(rule),
-        Rule::inline_list => parse_inline_list(rule),
-        Rule::inline_map => parse_inline_map(rule),
-        _ => unreachable!("Unexpected inline value rule: {:?}", rule.as_rule()),
+        Rule::duration => parse_duration(rule),
+        Rule::alias => anchors.resolve(&rule.as_str()[1..]),
+        Rule::inline_list => parse_inline_list(rule, anchors),
+        Rule::inline_map => parse_inline_map(rule, anchors),
+        other => Err(unexpected_rule(other)),
     }
 }
 
-fn parse_inline_list(pair: Pair<Rule>) -> Result<Value> {
+fn parse_inline_list(pair: Pair<Rule>, anchors: &mut Anchors) -> Result<Value> {
     let mut items = Vec::new();
 
     for inner in pair.into_inner() {
         if inner.as_rule() == Rule::inline_value {
-            items.push(parse_inline_value(inner)?);
+            items.push(parse_inline_value(inner, anchors)?);
         }
     }
 
     Ok(Value::List(items))
 }
 
-fn parse_inline_map(pair: Pair<Rule>) -> Result<Value> {
+fn parse_inline_map(pair: Pair<Rule>, anchors: &mut Anchors) -> Result<Value> {
     let mut map = BTreeMap::new();
 
     for member in pair.into_inner() {
         if member.as_rule() == Rule::inline_member {
             let mut inner = member.into_inner();
-            let key = parse_key(inner.next().unwrap())?;
-            let value = parse_inline_value(inner.next().unwrap())?;
+            let key = parse_key(next_pair(&mut inner, Rule::inline_member)?)?;
+            let value = parse_inline_value(next_pair(&mut inner, Rule::inline_member)?, anchors)?;
 
             if map.contains_key(&key) {
                 return Err(Error::DuplicateKey(key));
@@ -348,9 +491,8 @@ fn parse_float(pair: Pair<Rule>) -> Result<Value> {
 }
 
 fn parse_string(pair: Pair<Rule>) -> Result<Value> {
-    let mut inner = pair.into_inner();
-    let quoted = inner.next().unwrap();
-    let content_pair = quoted.into_inner().next().unwrap();
+    let quoted = first_inner(pair)?;
+    let content_pair = first_inner(quoted)?;
     let content = content_pair.as_str();
 
     let mut result = String::with_capacity(content.len());
@@ -407,17 +549,18 @@ fn parse_unicode_escape(chars: &mut std::str::Chars) -> Result<char> {
 }
 
 fn parse_binary(pair: Pair<Rule>) -> Result<Value> {
-    let rule = pair.into_inner().next().unwrap();
+    let rule = first_inner(pair)?;
 
     match rule.as_rule() {
         Rule::base64_binary => {
-            let content = rule.into_inner().next().unwrap().as_str();
+            let content = first_inner(rule)?.as_str();
+            // Only canonical base64 decodes, so the formatter writes it back unchanged
             let bytes =
                 base64::Engine::decode(&base64::engine::general_purpose::STANDARD, content)?;
             Ok(Value::Binary(Binary(bytes)))
         }
         Rule::hex_binary => {
-            let content = rule.into_inner().next().unwrap().as_str();
+            let content = first_inner(rule)?.as_str();
             if !content.len().is_multiple_of(2) {
                 return Err(Error::OddHexDigits);
             }
@@ -427,15 +570,42 @@ fn parse_binary(pair: Pair<Rule>) -> Result<Value> {
                 .collect::<StdResult<Vec<u8>, _>>()?;
             Ok(Value::Binary(Binary(bytes)))
         }
-        _ => unreachable!("Unexpected binary rule: {:?}", rule.as_rule()),
+        other => Err(unexpected_rule(other)),
     }
 }
 
 fn parse_timestamp(pair: Pair<Rule>) -> Result<Value> {
-    let content = pair.into_inner().next().unwrap().as_str();
+    let content = first_inner(pair)?.as_str();
 
     match time::OffsetDateTime::parse(content, &time::format_description::well_known::Rfc3339) {
         Ok(dt) => Ok(Value::Timestamp(dt)),
         Err(e) => Err(Error::InvalidTimestamp(content.to_string(), e.to_string())),
     }
 }
+
+fn parse_duration(pair: Pair<Rule>) -> Result<Value> {
+    let content = first_inner(pair)?.as_str();
+
+    match jasn_core::duration::from_iso8601(content) {
+        Ok(d) => Ok(Value::Duration(d)),
+        Err(e) => Err(Error::InvalidDuration(content.to_string(), e.to_string())),
+    }
+}
+
+/// Returns the next pair from `pairs`, or an internal error if the parse tree is shorter than
+/// the grammar guarantees.
+fn next_pair<'i>(pairs: &mut Pairs<'i, Rule>, parent: Rule) -> Result<Pair<'i, Rule>> {
+    pairs
+        .next()
+        .ok_or_else(|| Error::Internal(format!("missing child of {:?}", parent)))
+}
+
+/// Returns the first inner pair of `pair`, or an internal error if it has none.
+fn first_inner(pair: Pair<Rule>) -> Result<Pair<Rule>> {
+    let rule = pair.as_rule();
+    next_pair(&mut pair.into_inner(), rule)
+}
+
+fn unexpected_rule(rule: Rule) -> Error {
+    Error::Internal(format!("unexpected rule: {:?}", rule))
+}
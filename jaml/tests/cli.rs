@@ -74,6 +74,89 @@ fn test_format_check_invalid() {
         .stderr(predicate::str::contains("not formatted correctly"));
 }
 
+#[test]
+fn test_format_check_exit_codes() {
+    jaml_cmd()
+        .arg("format")
+        .arg("--check-format")
+        .write_stdin("test: 123\n")
+        .assert()
+        .code(0)
+        .stdout("")
+        .stderr("");
+
+    jaml_cmd()
+        .arg("format")
+        .arg("--check-format")
+        .write_stdin("# Comment\ntest: 123\n")
+        .assert()
+        .code(1)
+        .stdout("");
+}
+
+#[test]
+fn test_format_check_quiet() {
+    jaml_cmd()
+        .arg("format")
+        .arg("--check-format")
+        .arg("--quiet")
+        .write_stdin("# Comment\ntest: 123\n")
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("");
+
+    jaml_cmd()
+        .arg("format")
+        .arg("-q")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--check-format"));
+}
+
+#[test]
+fn test_format_diff() {
+    jaml_cmd()
+        .arg("format")
+        .arg("--diff")
+        .arg("--stdin-filename")
+        .arg("config.jaml")
+        .write_stdin("b: 1\na:   2\n")
+        .assert()
+        .code(1)
+        .stdout(concat!(
+            "--- config.jaml.orig\n",
+            "+++ config.jaml\n",
+            "@@ -1,2 +1,2 @@\n",
+            "+a: 2\n",
+            " b: 1\n",
+            "-a:   2\n",
+        ))
+        .stderr("");
+
+    jaml_cmd()
+        .arg("format")
+        .arg("--diff")
+        .write_stdin("a: 2\nb: 1\n")
+        .assert()
+        .code(0)
+        .stdout("")
+        .stderr("");
+}
+
+#[test]
+fn test_format_diff_with_check_format() {
+    jaml_cmd()
+        .arg("format")
+        .arg("--diff")
+        .arg("--check-format")
+        .write_stdin("b: 1\na: 2\n")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("+a: 2"))
+        .stderr(predicate::str::contains("not formatted correctly"));
+}
+
 #[test]
 fn test_check_valid_file() {
     let mut cmd = jaml_cmd();
@@ -94,6 +177,43 @@ fn test_check_invalid_file() {
         .stderr(predicate::str::contains("✗"));
 }
 
+#[test]
+fn test_stdin_filename() {
+    let mut cmd = jaml_cmd();
+    cmd.arg("check")
+        .arg("--stdin-filename")
+        .arg("foo.jaml")
+        .write_stdin("a: 1\na: 2\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Error: foo.jaml: Invalid JAML syntax",
+        ));
+
+    let mut cmd = jaml_cmd();
+    cmd.arg("format")
+        .arg("--stdin-filename")
+        .arg("foo.jaml")
+        .write_stdin("a: 1\na: 2\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Failed to parse JAML from foo.jaml",
+        ));
+
+    let mut cmd = jaml_cmd();
+    cmd.arg("format")
+        .arg("--check-format")
+        .arg("--stdin-filename")
+        .arg("foo.jaml")
+        .write_stdin("b: 2\na: 1\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "File 'foo.jaml' is not formatted correctly",
+        ));
+}
+
 #[test]
 fn test_check_multiple_files() {
     let mut cmd = jaml_cmd();
@@ -159,6 +279,74 @@ fn test_check_verbose() {
         .stdout(predicate::str::contains("Valid JAML:"));
 }
 
+#[test]
+fn test_check_parse_tree() {
+    let mut cmd = jaml_cmd();
+    cmd.arg("check")
+        .arg("--parse-tree")
+        .write_stdin("a:\n  - 1\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("jaml 1:1"))
+        .stdout(predicate::str::contains("map_entry 1:1 0..2 \"a:\""))
+        .stdout(predicate::str::contains("list_item 2:3 5..8 \"- 1\""))
+        .stdout(predicate::str::contains("integer 2:5 7..8 \"1\""));
+}
+
+#[test]
+fn test_check_parse_tree_hidden_from_help() {
+    let mut cmd = jaml_cmd();
+    cmd.arg("check")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("parse-tree").not());
+}
+
+#[test]
+fn test_format_header() {
+    let mut cmd = jaml_cmd();
+    cmd.arg("format")
+        .arg("--header")
+        .arg("Generated by X\ndo not edit")
+        .write_stdin(r#"test: 123"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "# Generated by X\n# do not edit\ntest: 123\n",
+        ));
+}
+
+#[test]
+fn test_format_raw_output_string() {
+    let mut cmd = jaml_cmd();
+    cmd.arg("format")
+        .arg("-r")
+        .write_stdin(r#""line\n\"quoted\" caf\u00e9""#)
+        .assert()
+        .success()
+        .stdout("line\n\"quoted\" café\n");
+}
+
+#[test]
+fn test_format_raw_output_non_string() {
+    let mut cmd = jaml_cmd();
+    cmd.arg("format")
+        .arg("--raw-output")
+        .write_stdin("42")
+        .assert()
+        .success()
+        .stdout("42\n");
+
+    let mut cmd = jaml_cmd();
+    cmd.arg("format")
+        .arg("--raw-output")
+        .write_stdin(r#"a: "x""#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#"a: "x""#));
+}
+
 #[test]
 fn test_completions_bash() {
     let mut cmd = jaml_cmd();
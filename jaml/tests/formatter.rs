@@ -50,3 +50,151 @@ fn test_round_trip() {
     let reparsed = parse(&formatted).unwrap();
     assert_eq!(value, reparsed);
 }
+
+#[test]
+fn test_escaped_keys() {
+    let value = parse("\"a\\tb\": 1\n\"a\\\"b\": 2\n").unwrap();
+    let map = value.as_map().unwrap();
+    assert_eq!(map["a\tb"], 1);
+    assert_eq!(map["a\"b"], 2);
+
+    let formatted = format(&value);
+    assert!(formatted.contains("\"a\\tb\": 1"));
+    assert!(formatted.contains("\"a\\\"b\": 2"));
+    assert_eq!(parse(&formatted).unwrap(), value);
+}
+
+#[test]
+fn test_to_string_with_header() {
+    use jaml::formatter::{Options, to_string_with_header};
+
+    let value = parse("a: 1\nb:\n  - \"x\"\n").unwrap();
+    let text = to_string_with_header(
+        &value,
+        "Generated by X, do not edit\n\nRegenerate with `make`",
+        &Options::default(),
+    );
+    assert!(text.starts_with("# Generated by X, do not edit\n#\n# Regenerate with `make`\na: 1\n"));
+    assert_eq!(parse(&text).unwrap(), value);
+}
+
+#[test]
+fn test_keys_with_colons_and_spaces() {
+    let value = Value::from([
+        ("a: b", Value::Int(1)),
+        ("has space", Value::Int(2)),
+        (" padded ", Value::Int(3)),
+        ("nested:", Value::from([("x: y", Value::from([1, 2]))])),
+    ]);
+
+    let formatted = format(&value);
+    assert!(formatted.contains("\"a: b\": 1"));
+    assert!(formatted.contains("\"has space\": 2"));
+    assert!(formatted.contains("\" padded \": 3"));
+    assert!(formatted.contains("  \"x: y\":\n"));
+    assert_eq!(parse(&formatted).unwrap(), value);
+
+    // Inline maps too
+    let value = parse(r#"m: {"a: b": 1, 'c :d': "e: f"}"#).unwrap();
+    let map = value.as_map().unwrap()["m"].as_map().unwrap();
+    assert_eq!(map["a: b"], 1);
+    assert_eq!(map["c :d"], "e: f");
+}
+
+#[test]
+fn test_custom_indent_round_trip() {
+    use jaml::formatter::{Options, format_with_opts};
+
+    let value = Value::from([
+        (
+            "items",
+            Value::from([Value::from([("x", Value::Int(1))]), Value::from([2, 3])]),
+        ),
+        ("nested", Value::from([("a", Value::from([("b", true)]))])),
+    ]);
+
+    for indent in ["    ", "\t", " "] {
+        let formatted = format_with_opts(&value, &Options::new().with_indent(indent));
+        assert!(
+            formatted.contains(&format!("\n{indent}{indent}b: true\n")),
+            "{formatted}"
+        );
+        assert_eq!(parse(&formatted).unwrap(), value, "{indent:?}");
+    }
+}
+
+#[test]
+fn test_line_separators_always_escaped() {
+    use jaml::formatter::{Options, format_with_opts};
+
+    let value = Value::from([("text", "a\u{2028}b\u{2029}c")]);
+    for escape_unicode in [false, true] {
+        let opts = Options::default().with_escape_unicode(escape_unicode);
+        let formatted = format_with_opts(&value, &opts);
+        assert_eq!(formatted, "text: \"a\\u2028b\\u2029c\"\n");
+        assert_eq!(parse(&formatted).unwrap(), value);
+    }
+}
+
+#[test]
+fn test_hash_is_always_quoted() {
+    use jaml::formatter::{Options, format_with_opts};
+
+    let value = Value::from([
+        ("c#d", Value::from("a # b")),
+        ("#", Value::from(["#x", "y #"])),
+        ("nested", Value::from([("k#", Value::from("#"))])),
+    ]);
+    for opts in [
+        Options::default(),
+        Options::default().with_unquoted_keys(true),
+    ] {
+        let formatted = format_with_opts(&value, &opts);
+        assert!(formatted.contains("\"c#d\": \"a # b\"\n"), "{formatted}");
+        assert!(formatted.contains("\"#\":\n"), "{formatted}");
+        assert!(formatted.contains("- \"#x\"\n"), "{formatted}");
+        assert!(formatted.contains("\"k#\": \"#\"\n"), "{formatted}");
+        // Every `#` in the output sits inside quotes, so none starts a comment
+        for line in formatted.lines().filter(|line| line.contains('#')) {
+            let before_hash = &line[..line.find('#').unwrap()];
+            assert_eq!(before_hash.matches('"').count() % 2, 1, "{line}");
+        }
+        assert_eq!(parse(&formatted).unwrap(), value);
+    }
+}
+
+#[test]
+fn test_duration_round_trip() {
+    use std::time::Duration;
+
+    let value = Value::from([
+        ("session", Value::Duration(Duration::from_secs(5400))),
+        (
+            "retention",
+            Value::Duration(Duration::from_secs(9 * 86_400)),
+        ),
+        ("precise", Value::Duration(Duration::from_millis(125))),
+        ("zero", Value::Duration(Duration::ZERO)),
+    ]);
+    let formatted = format(&value);
+    assert!(
+        formatted.contains("session: dur\"PT1H30M\"\n"),
+        "{formatted}"
+    );
+    assert!(formatted.contains("retention: dur\"P9D\"\n"), "{formatted}");
+    assert!(
+        formatted.contains("precise: dur\"PT0.125S\"\n"),
+        "{formatted}"
+    );
+    assert!(formatted.contains("zero: dur\"PT0S\"\n"), "{formatted}");
+    assert_eq!(parse(&formatted).unwrap(), value);
+
+    assert_eq!(
+        parse("d: dur\"P1W1D\"").unwrap(),
+        Value::from([("d", Value::Duration(Duration::from_secs(8 * 86_400)))])
+    );
+    assert!(matches!(
+        parse("d: dur\"P1Y\""),
+        Err(jaml::ParseError::InvalidDuration(ref text, _)) if text == "P1Y"
+    ));
+}
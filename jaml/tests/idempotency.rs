@@ -0,0 +1,69 @@
+//! Formatting already-formatted output must not change it.
+
+use std::{fs, path::Path};
+
+use jaml::{
+    format_with_opts,
+    formatter::{BinaryEncoding, Options, QuoteStyle, TimestampPrecision},
+    parse,
+};
+
+fn option_sets() -> Vec<(&'static str, Options)> {
+    vec![
+        ("default", Options::default()),
+        ("tabs", Options::default().with_indent("\t")),
+        ("four_spaces", Options::default().with_indent("    ")),
+        (
+            "single_quotes",
+            Options::default().with_quote_style(QuoteStyle::Single),
+        ),
+        ("quoted_keys", Options::default().with_unquoted_keys(false)),
+        (
+            "hex",
+            Options::default().with_binary_encoding(BinaryEncoding::Hex),
+        ),
+        ("leading_plus", Options::default().with_leading_plus(true)),
+        ("unsorted", Options::default().with_sort_keys(false)),
+        (
+            "escape_unicode",
+            Options::default().with_escape_unicode(true),
+        ),
+        ("offset_timestamps", Options::default().with_use_zulu(false)),
+        (
+            "seconds",
+            Options::default().with_timestamp_precision(TimestampPrecision::Seconds),
+        ),
+    ]
+}
+
+#[test]
+fn test_valid_examples_format_idempotently() {
+    let mut examples: Vec<_> = fs::read_dir(Path::new("examples/valid"))
+        .expect("Failed to read valid examples directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jaml"))
+        .collect();
+    examples.sort();
+    assert!(!examples.is_empty(), "No valid examples found");
+
+    for example in examples {
+        let content = fs::read_to_string(&example).unwrap();
+        let Ok(value) = parse(&content) else {
+            // Empty documents are valid examples but have no value to format
+            continue;
+        };
+        for (label, opts) in option_sets() {
+            let once = format_with_opts(&value, &opts);
+            let reparsed = parse(&once).unwrap_or_else(|e| {
+                panic!("{} [{label}] does not reparse: {e}", example.display())
+            });
+            let twice = format_with_opts(&reparsed, &opts);
+            assert_eq!(
+                twice,
+                once,
+                "{} [{label}] is not idempotent",
+                example.display()
+            );
+        }
+    }
+}
@@ -34,3 +34,132 @@ fn test_all_invalid_examples() {
         );
     }
 }
+
+#[test]
+fn test_structured_error_variants() {
+    use jaml::ParseError;
+
+    assert!(matches!(
+        parse(r#"hex"ABC""#),
+        Err(ParseError::OddHexDigits)
+    ));
+    assert!(matches!(
+        parse("9223372036854775808"),
+        Err(ParseError::ParseIntError(_))
+    ));
+    assert!(matches!(
+        parse("a: 1\na: 2"),
+        Err(ParseError::DuplicateKey(ref key)) if key == "a"
+    ));
+    assert!(matches!(parse(""), Err(ParseError::EmptyDocument)));
+    assert!(matches!(parse("key"), Err(ParseError::PestError(_))));
+    assert!(matches!(
+        parse("a: *missing"),
+        Err(ParseError::UndefinedAlias(ref name)) if name == "missing"
+    ));
+    assert!(matches!(
+        parse("a: &self\n  b: *self"),
+        Err(ParseError::CyclicAlias(ref name)) if name == "self"
+    ));
+    // Aliases must come after their anchor
+    assert!(matches!(
+        parse("a: *x\nb: &x 1"),
+        Err(ParseError::UndefinedAlias(ref name)) if name == "x"
+    ));
+}
+
+#[test]
+fn test_alias_bomb() {
+    use jaml::ParseError;
+
+    // Each level holds ten aliases of the previous one: 10^9 values from under 500 bytes
+    let mut input = format!("l0: &l0 [{}]\n", ["1"; 10].join(", "));
+    for level in 1..9 {
+        let aliases = vec![format!("*l{}", level - 1); 10].join(", ");
+        input.push_str(&format!("l{level}: &l{level} [{aliases}]\n"));
+    }
+    assert!(matches!(
+        parse(&input),
+        Err(ParseError::AliasLimitExceeded(1_000_000))
+    ));
+}
+
+#[test]
+fn test_alias_limit() {
+    use jaml::{ParseError, ParseOptions, parse_with_opts};
+
+    // Each alias of `[1, 2]` copies three values
+    let input = "a: &a [1, 2]\nb: *a\nc: [*a]";
+    let opts = ParseOptions::new().with_max_alias_nodes(Some(6));
+    assert!(parse_with_opts(input, &opts).is_ok());
+    let opts = ParseOptions::new().with_max_alias_nodes(Some(5));
+    assert!(matches!(
+        parse_with_opts(input, &opts),
+        Err(ParseError::AliasLimitExceeded(5))
+    ));
+    let opts = ParseOptions::new().with_max_alias_nodes(None);
+    assert!(parse_with_opts(input, &opts).is_ok());
+}
+
+#[test]
+fn test_error_line_numbers() {
+    use jaml::ParseError;
+
+    // Key without a value on line 3, followed by a sibling
+    assert!(matches!(
+        parse("a: 1\nb: 2\nc:\nd: 4"),
+        Err(ParseError::MissingValue(3))
+    ));
+    // Key without a value on the last line, after blank and comment lines
+    assert!(matches!(
+        parse("a: 1\n\n# comment\nb:\n"),
+        Err(ParseError::MissingValue(4))
+    ));
+    // Same with CRLF line endings
+    assert!(matches!(
+        parse("a: 1\r\n\r\nb:"),
+        Err(ParseError::MissingValue(3))
+    ));
+    // Mixed tabs and spaces on line 3
+    assert!(matches!(
+        parse("a:\n  b: 1\n \tc: 2"),
+        Err(ParseError::MixedIndent(3, _))
+    ));
+    // Switching from spaces to tabs on line 4
+    assert!(matches!(
+        parse("a:\n  b: 1\n\n\tc: 2"),
+        Err(ParseError::InconsistentIndentTab(4, _, _))
+    ));
+    // Indent that is not a multiple of the base unit on line 3
+    assert!(matches!(
+        parse("a:\n  b: 1\n   c: 2"),
+        Err(ParseError::InvalidIndentCount(3, 2, 3))
+    ));
+    // Over-indented sibling on line 3
+    assert!(matches!(
+        parse("a:\n  b: 1\n    c: 2"),
+        Err(ParseError::UnexpectedIndent(3, 1, 2))
+    ));
+
+    let err = parse("a:\n  b: 1\n    c: 2").unwrap_err();
+    assert!(err.to_string().contains("at line 3"));
+}
+
+#[test]
+fn test_empty_document() {
+    use jaml::ParseError;
+
+    for input in [
+        "",
+        "  ",
+        "\n\n",
+        "  \n\t\n",
+        "# just a comment",
+        "\n# one\n  # two\n",
+    ] {
+        assert!(
+            matches!(parse(input), Err(ParseError::EmptyDocument)),
+            "{input:?} should be an empty document"
+        );
+    }
+}
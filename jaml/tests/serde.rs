@@ -198,6 +198,29 @@ fn test_deserialize_enum_unit() {
     assert_eq!(color, Color::Green);
 }
 
+#[test]
+fn test_deserialize_enum_unit_field() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Data {
+        color: Color,
+    }
+
+    let data: Data = jaml::from_str(r#"color: "Green""#).unwrap();
+    assert_eq!(data.color, Color::Green);
+
+    let data: Data = jaml::from_str("color: 'Red'").unwrap();
+    assert_eq!(data.color, Color::Red);
+
+    // JAML has no bareword strings, so an unquoted variant name is a syntax error
+    assert!(jaml::from_str::<Data>("color: Green").is_err());
+}
+
 #[test]
 fn test_deserialize_enum_newtype() {
     #[derive(Deserialize, Debug, PartialEq)]
@@ -434,3 +457,44 @@ fn test_inline_map_syntax() {
     assert_eq!(data.point.x, 10);
     assert_eq!(data.point.y, 20);
 }
+
+#[test]
+fn test_to_string_opts_at_crate_root() {
+    use jaml::formatter::{Options, QuoteStyle};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Data {
+        name: String,
+        count: i32,
+    }
+
+    let data = Data {
+        name: "x".to_string(),
+        count: 3,
+    };
+    let opts = Options::new()
+        .with_quote_style(QuoteStyle::Single)
+        .with_leading_plus(true);
+    let jaml = jaml::to_string_opts(&data, &opts).unwrap();
+    assert_eq!(jaml, "count: +3\nname: 'x'\n");
+
+    let value = jaml::parse(&jaml).unwrap();
+    assert_eq!(jaml::from_value::<Data>(&value).unwrap(), data);
+    assert_eq!(jaml::de::from_str::<Data>(&jaml).unwrap(), data);
+}
+
+#[test]
+fn test_from_str_empty_input() {
+    use jaml::{ParseError, de::Error};
+
+    for input in ["", "  # nothing here\n"] {
+        let err = jaml::from_str::<i64>(input).unwrap_err();
+        assert!(matches!(err, Error::ParseError(ParseError::EmptyDocument)));
+        assert_eq!(err.to_string(), "Parse error: Empty document");
+
+        // An empty document is not `None`
+        let err = jaml::from_str::<Option<i64>>(input).unwrap_err();
+        assert!(matches!(err, Error::ParseError(ParseError::EmptyDocument)));
+    }
+    assert_eq!(jaml::from_str::<Option<i64>>("null").unwrap(), None);
+}
@@ -27,8 +27,17 @@ fn test_all_valid_examples() {
         let content = fs::read_to_string(&example)
             .unwrap_or_else(|e| panic!("Failed to read {:?}: {}", example, e));
 
-        parse(&content)
+        let value = parse(&content)
             .unwrap_or_else(|e| panic!("Failed to parse valid example {:?}: {}", example, e));
+
+        let crlf = content.replace("\r\n", "\n").replace('\n', "\r\n");
+        let crlf_value =
+            parse(&crlf).unwrap_or_else(|e| panic!("Failed to parse CRLF {:?}: {}", example, e));
+        assert!(
+            crlf_value.structural_eq(&value),
+            "CRLF version of {:?} parses differently",
+            example
+        );
     }
 }
 
@@ -51,6 +60,24 @@ fn test_integers() {
     ));
 }
 
+#[test]
+fn test_integer_prefix_case() {
+    for (input, expected) in [
+        ("0XfF", 255),
+        ("0B1010", 10),
+        ("0O17", 15),
+        ("0xFF_FF", 0xFFFF),
+        ("-0Xa", -10),
+    ] {
+        let value = parse(input).unwrap();
+        assert_eq!(value, jaml::Value::Int(expected), "{input}");
+        // The formatter always writes decimal
+        let formatted = jaml::format(&value);
+        assert_eq!(formatted.trim(), expected.to_string());
+        assert_eq!(parse(&formatted).unwrap(), value);
+    }
+}
+
 #[test]
 fn test_floats() {
     assert!(matches!(parse("3.14").unwrap(), jaml::Value::Float(_)));
@@ -62,6 +89,26 @@ fn test_floats() {
     assert!(matches!(parse("nan").unwrap(), jaml::Value::Float(f) if f.is_nan()));
 }
 
+#[test]
+fn test_float_exponents() {
+    for (input, expected) in [
+        ("1E10", 1e10),
+        ("2E+5", 2e5),
+        ("3.0e-7", 3.0e-7),
+        ("5e0", 5.0),
+        ("-1.5E-3", -1.5e-3),
+    ] {
+        assert_eq!(
+            parse(input).unwrap(),
+            jaml::Value::Float(expected),
+            "{input}"
+        );
+    }
+    // An exponent needs digits
+    assert!(parse("1e").is_err());
+    assert!(parse("1E+").is_err());
+}
+
 #[test]
 fn test_strings() {
     assert!(matches!(parse(r#""hello""#).unwrap(), jaml::Value::String(s) if s == "hello"));
@@ -105,6 +152,22 @@ fn test_comments() {
     assert!(matches!(result, jaml::Value::Map(_)));
 }
 
+#[test]
+fn test_trailing_comments_after_root_value() {
+    assert_eq!(parse("42 # note").unwrap(), jaml::Value::Int(42));
+    assert_eq!(parse("42\t# note\n# end\n").unwrap(), jaml::Value::Int(42));
+    assert_eq!(
+        parse("{a: 1} # end").unwrap(),
+        jaml::Value::from([("a", 1)])
+    );
+    assert_eq!(parse("[1, 2] #end\n").unwrap(), jaml::Value::from([1, 2]));
+
+    // Trailing values are still rejected
+    assert!(parse("42 43").is_err());
+    assert!(parse("42 # note\n43").is_err());
+    assert!(parse("{a: 1} {b: 2}").is_err());
+}
+
 #[test]
 fn test_nested_structures() {
     let result = parse("outer:\n  inner: \"value\"").unwrap();
@@ -147,3 +210,110 @@ fn test_trailing_whitespace() {
     let result = parse("a: 1\n   \nb: 2\n\t\t\nc: 3").unwrap();
     assert!(matches!(result, jaml::Value::Map(ref m) if m.len() == 3));
 }
+
+#[test]
+fn test_signed_special_floats_in_collections() {
+    let result = parse("w: inf\nx: -inf\ny: nan\nz: +inf").unwrap();
+    let map = result.as_map().unwrap();
+    assert_eq!(map["w"], f64::INFINITY);
+    assert_eq!(map["x"], f64::NEG_INFINITY);
+    assert!(map["y"].as_float().unwrap().is_nan());
+    assert_eq!(map["z"], f64::INFINITY);
+
+    let result = parse("- inf\n- -inf\n- nan\n- +inf").unwrap();
+    let list = result.as_list().unwrap();
+    assert_eq!(list[0], f64::INFINITY);
+    assert_eq!(list[1], f64::NEG_INFINITY);
+    assert!(list[2].as_float().unwrap().is_nan());
+    assert_eq!(list[3], f64::INFINITY);
+
+    let result = parse("{x: -inf, y: nan, z: +inf}").unwrap();
+    let map = result.as_map().unwrap();
+    assert_eq!(map["x"], f64::NEG_INFINITY);
+    assert!(map["y"].as_float().unwrap().is_nan());
+    assert_eq!(map["z"], f64::INFINITY);
+}
+
+#[test]
+fn test_anchors_and_aliases() {
+    let result = parse(
+        "defaults: &defaults\n  adapter: \"postgres\"\n  port: 5432\ndev:\n  settings: *defaults\nprod: *defaults",
+    )
+    .unwrap();
+    let map = result.as_map().unwrap();
+    assert_eq!(map["dev"].as_map().unwrap()["settings"], map["defaults"]);
+    assert_eq!(map["prod"], map["defaults"]);
+    assert_eq!(map["defaults"].as_map().unwrap()["port"], 5432);
+
+    // Inline anchors, and aliases inside inline collections
+    let result = parse("- &x 1\n- *x\n- [*x, {y: *x}]").unwrap();
+    let list = result.as_list().unwrap();
+    assert_eq!(list[1], 1);
+    assert_eq!(list[2].as_list().unwrap()[0], 1);
+    assert_eq!(list[2].as_list().unwrap()[1].as_map().unwrap()["y"], 1);
+}
+
+#[test]
+fn test_bare_key_is_null() {
+    use jaml::{ParseError, ParseOptions, Value, parse_with_opts};
+
+    let opts = ParseOptions::new().with_bare_key_is_null(true);
+
+    let value = parse_with_opts("a:\nb: 1", &opts).unwrap();
+    assert_eq!(
+        value,
+        Value::from([("a", Value::Null), ("b", Value::Int(1))])
+    );
+
+    // Last line, comments, nesting and anchors
+    let value = parse_with_opts("x:\n  y: # note\n  z: 2\nlast:\n", &opts).unwrap();
+    assert_eq!(
+        value,
+        Value::from([
+            ("x", Value::from([("y", Value::Null), ("z", Value::Int(2))])),
+            ("last", Value::Null),
+        ])
+    );
+    let value = parse_with_opts("a: &empty\nb: *empty", &opts).unwrap();
+    assert_eq!(value, Value::from([("a", Value::Null), ("b", Value::Null)]));
+
+    // An indented block below the key is still its value
+    let value = parse_with_opts("a:\n  - 1\nb:", &opts).unwrap();
+    assert_eq!(
+        value,
+        Value::from([("a", Value::from([1])), ("b", Value::Null)])
+    );
+
+    // Without the option a bare key is still an error, and list items always need a value
+    assert!(matches!(
+        parse("a:\nb: 1"),
+        Err(ParseError::MissingValue(1))
+    ));
+    assert!(matches!(
+        parse_with_opts("- 1\n-\n", &opts),
+        Err(ParseError::MissingValue(2))
+    ));
+}
+
+#[test]
+fn test_crlf_matches_lf() {
+    let documents = [
+        "# header\nname: \"app\" # trailing\n\nservers:\n  -\n    host: \"a\"\n    port: 80\n  -\n    - 1\n    - [2, 3]\n",
+        "root:\n\tkey: &v \"x\"   \n\t\n\tother: *v\n",
+        "- 1\n- {a: 1}\n# done",
+    ];
+    for lf in documents {
+        let crlf = lf.replace('\n', "\r\n");
+        assert_eq!(parse(&crlf).unwrap(), parse(lf).unwrap(), "{lf:?}");
+    }
+
+    // Errors report the same line numbers
+    for lf in ["a:\n  b: 1\n    c: 2\n", "a: 1\n\nb:\n"] {
+        let crlf = lf.replace('\n', "\r\n");
+        assert_eq!(
+            parse(&crlf).unwrap_err().to_string(),
+            parse(lf).unwrap_err().to_string(),
+            "{lf:?}"
+        );
+    }
+}
@@ -20,12 +20,35 @@
 //!
 //! # Features
 //!
+//! - `std` (default): Use the standard library. Without it the crate is `no_std` and only needs
+//!   `alloc`, so [`Value`] and [`Binary`] can be used on embedded and WASM targets.
 //! - `serde` (default): Enable serde serialization/deserialization support
+//! - `bytes`: Conversions between [`Binary`] and `bytes::Bytes`, moving the buffer where possible
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
 mod value;
-pub use value::{Binary, Timestamp, Value};
+pub use value::{
+    Binary, DurationError, Index, JS_MAX_SAFE_INTEGER, PatchError, Timestamp, TryFromValueError,
+    TypeCounts, Value,
+};
+
+pub mod duration {
+    //! Helpers for reading and writing [`Value::Duration`](crate::Value::Duration) values as
+    //! ISO 8601 durations.
+    pub use crate::value::duration::{from_iso8601, to_iso8601};
+}
+
+pub mod timestamp {
+    //! Helpers for building and rendering [`Timestamp`](crate::Timestamp) values without
+    //! calling `time` APIs directly.
+    #[cfg(feature = "std")]
+    pub use crate::value::timestamp::now;
+    pub use crate::value::timestamp::{from_rfc3339, from_unix, to_rfc3339};
+}
 
 #[cfg(feature = "serde")]
 pub mod de {
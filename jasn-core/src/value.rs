@@ -1,8 +1,24 @@
-use std::{borrow::Cow, collections::BTreeMap};
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::time::Duration;
 
 mod binary;
 pub use binary::Binary;
-mod timestamp;
+mod counts;
+pub use counts::TypeCounts;
+mod debug;
+pub(crate) mod duration;
+pub use duration::DurationError;
+mod flatten;
+mod patch;
+pub use patch::PatchError;
+mod pointer;
+mod schema;
+pub(crate) mod timestamp;
 pub use timestamp::Timestamp;
 
 #[cfg(feature = "serde")]
@@ -10,8 +26,42 @@ pub mod de;
 #[cfg(feature = "serde")]
 pub mod ser;
 
+/// Largest integer magnitude that an `f64` (and so a JavaScript number) represents exactly.
+///
+/// See [`Value::has_js_unsafe_integers`].
+pub const JS_MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// A map key or list position, used by [`Value::get`] and [`Value::get_mut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Index<'a> {
+    /// Key into a [`Value::Map`].
+    Key(&'a str),
+    /// Position in a [`Value::List`].
+    Position(usize),
+}
+
+impl<'a> From<&'a str> for Index<'a> {
+    fn from(key: &'a str) -> Self {
+        Index::Key(key)
+    }
+}
+
+impl<'a> From<&'a String> for Index<'a> {
+    fn from(key: &'a String) -> Self {
+        Index::Key(key)
+    }
+}
+
+impl From<usize> for Index<'_> {
+    fn from(position: usize) -> Self {
+        Index::Position(position)
+    }
+}
+
 /// Represents a valid JASN value.
-#[derive(Debug, Clone, PartialEq, Default)]
+///
+/// `{:#?}` prints the value as JASN-like pretty text; `{:?}` shows the enum variants.
+#[derive(Clone, PartialEq, Default)]
 pub enum Value {
     /// Null value.
     #[default]
@@ -28,6 +78,8 @@ pub enum Value {
     Binary(Binary),
     /// Timestamp with timezone (ISO8601/RFC3339 compatible).
     Timestamp(Timestamp),
+    /// Fixed-length span of time (ISO 8601 duration without years or months).
+    Duration(Duration),
     /// Ordered list of values.
     List(Vec<Value>),
     /// Map of string keys to values.
@@ -37,13 +89,48 @@ pub enum Value {
 /// Display implementation for Value using debug formatting.
 ///
 /// For proper JASN formatting, use the `jasn` crate's formatting functions.
-impl std::fmt::Display for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
 impl Value {
+    /// Creates a [`Self::Bool`].
+    pub fn bool(value: bool) -> Self {
+        Value::Bool(value)
+    }
+
+    /// Creates a [`Self::Int`].
+    pub fn int(value: i64) -> Self {
+        Value::Int(value)
+    }
+
+    /// Creates a [`Self::Float`].
+    pub fn float(value: f64) -> Self {
+        Value::Float(value)
+    }
+
+    /// Creates a [`Self::String`].
+    pub fn string(value: impl Into<String>) -> Self {
+        Value::String(value.into())
+    }
+
+    /// Creates a [`Self::Binary`].
+    pub fn binary(value: impl Into<Binary>) -> Self {
+        Value::Binary(value.into())
+    }
+
+    /// Creates a [`Self::Timestamp`].
+    pub fn timestamp(value: Timestamp) -> Self {
+        Value::Timestamp(value)
+    }
+
+    /// Creates a [`Self::Duration`].
+    pub fn duration(value: Duration) -> Self {
+        Value::Duration(value)
+    }
+
     /// Returns true if the value is [`Self::Null`].
     pub fn is_null(&self) -> bool {
         matches!(self, Value::Null)
@@ -79,6 +166,11 @@ impl Value {
         matches!(self, Value::Timestamp(_))
     }
 
+    /// Returns true if the value is [`Self::Duration`].
+    pub fn is_duration(&self) -> bool {
+        matches!(self, Value::Duration(_))
+    }
+
     /// Returns true if the value is [`Self::List`].
     pub fn is_list(&self) -> bool {
         matches!(self, Value::List(_))
@@ -129,6 +221,16 @@ impl Value {
         }
     }
 
+    /// Returns the raw bytes if this is a [`Self::Binary`], or the UTF-8 bytes if this is a
+    /// [`Self::String`], otherwise `None`.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::String(s) => Some(s.as_bytes()),
+            Value::Binary(b) => Some(b),
+            _ => None,
+        }
+    }
+
     /// Returns the [`Timestamp`] value if this is a [`Self::Timestamp`], otherwise `None`.
     pub fn as_timestamp(&self) -> Option<&Timestamp> {
         match self {
@@ -137,6 +239,14 @@ impl Value {
         }
     }
 
+    /// Returns the [`Duration`] if this is a [`Self::Duration`], otherwise `None`.
+    pub fn as_duration(&self) -> Option<Duration> {
+        match self {
+            Value::Duration(d) => Some(*d),
+            _ => None,
+        }
+    }
+
     /// Returns the list of values if this is a [`Self::List`], otherwise `None`.
     pub fn as_list(&self) -> Option<&[Value]> {
         match self {
@@ -145,6 +255,35 @@ impl Value {
         }
     }
 
+    /// Converts a [`Self::List`] into a `Vec<T>`, converting each element with `T`'s
+    /// [`TryFrom<&Value>`] implementation.
+    ///
+    /// This is the borrowing form of `Vec::<T>::try_from(value)`: only the converted scalars are
+    /// cloned. The error reports the first element that does not convert, or that the value is
+    /// not a list.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let value = Value::from([1, 2, 3]);
+    /// assert_eq!(value.as_array_of::<i64>().unwrap(), [1, 2, 3]);
+    ///
+    /// let err = Value::from([Value::Int(1), Value::Null]).as_array_of::<i64>().unwrap_err();
+    /// assert_eq!(err.to_string(), "expected int, got null at [1]");
+    /// ```
+    pub fn as_array_of<T>(&self) -> Result<Vec<T>, TryFromValueError>
+    where
+        T: for<'a> TryFrom<&'a Value, Error = TryFromValueError>,
+    {
+        let list = self
+            .as_list()
+            .ok_or_else(|| TryFromValueError::new("list", self))?;
+        list.iter()
+            .enumerate()
+            .map(|(i, item)| T::try_from(item).map_err(|e| e.within(Index::Position(i))))
+            .collect()
+    }
+
     /// Returns the map of key-value pairs if this is a [`Self::Map`], otherwise `None`.
     pub fn as_map(&self) -> Option<&BTreeMap<String, Value>> {
         match self {
@@ -169,12 +308,367 @@ impl Value {
         }
     }
 
+    /// Returns the entries of a [`Self::Map`] in key order, otherwise `None`.
+    ///
+    /// This is an alias of [`Self::as_map`]; prefer [`Self::map_keys`] and
+    /// [`Self::map_values`] when the concrete map type does not matter.
+    pub fn map_entries(&self) -> Option<&BTreeMap<String, Value>> {
+        self.as_map()
+    }
+
+    /// Returns an iterator over the keys of a [`Self::Map`] in key order, otherwise `None`.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let value = Value::from([("b", 2), ("a", 1)]);
+    /// assert_eq!(value.map_keys().unwrap().collect::<Vec<_>>(), ["a", "b"]);
+    /// ```
+    pub fn map_keys(&self) -> Option<impl Iterator<Item = &str>> {
+        self.as_map().map(|m| m.keys().map(String::as_str))
+    }
+
+    /// Returns an iterator over the values of a [`Self::Map`] in key order, otherwise `None`.
+    pub fn map_values(&self) -> Option<impl Iterator<Item = &Value>> {
+        self.as_map().map(|m| m.values())
+    }
+
+    /// Returns the map entry for a key or the list element at a position.
+    ///
+    /// Returns `None` if the key or position is missing, or if the index kind does not match
+    /// the value (a key on a list, a position on a map, or any index on a scalar).
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let value = Value::from([("list", [10, 20])]);
+    /// assert_eq!(value.get("list").and_then(|list| list.get(1)), Some(&Value::Int(20)));
+    /// assert_eq!(value.get(0), None);
+    /// ```
+    pub fn get<'a>(&self, index: impl Into<Index<'a>>) -> Option<&Value> {
+        match (self, index.into()) {
+            (Value::Map(map), Index::Key(key)) => map.get(key),
+            (Value::List(list), Index::Position(i)) => list.get(i),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the map entry for a key or the list element at a
+    /// position; see [`Self::get`].
+    pub fn get_mut<'a>(&mut self, index: impl Into<Index<'a>>) -> Option<&mut Value> {
+        match (self, index.into()) {
+            (Value::Map(map), Index::Key(key)) => map.get_mut(key),
+            (Value::List(list), Index::Position(i)) => list.get_mut(i),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value at a dotted path of map keys, creating it
+    /// if needed.
+    ///
+    /// Missing keys are inserted as [`Self::Null`], and a [`Self::Null`] on the way (including
+    /// `self`) becomes an empty map. Returns `None`, leaving the value untouched, if the path
+    /// runs into a list or another scalar. Each segment is a literal key, so list elements
+    /// cannot be addressed; the empty path returns `self`.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let mut value = Value::Map(Default::default());
+    /// *value.entry_path("server.port").unwrap() = Value::Int(8080);
+    /// *value.entry_path("server.host").unwrap() = Value::from("localhost");
+    /// assert_eq!(value.pointer("/server/port"), Some(&Value::Int(8080)));
+    /// assert!(value.entry_path("server.port.number").is_none());
+    /// ```
+    pub fn entry_path(&mut self, path: &str) -> Option<&mut Value> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        path.split('.')
+            .try_fold(self, |target, key| target.entry_key(key))
+    }
+
+    /// Returns the map entry for `key`, inserting [`Self::Null`] if it is missing and turning a
+    /// [`Self::Null`] `self` into an empty map first.
+    fn entry_key(&mut self, key: &str) -> Option<&mut Value> {
+        if self.is_null() {
+            *self = Value::Map(BTreeMap::new());
+        }
+        match self {
+            Value::Map(map) => Some(map.entry(key.to_string()).or_default()),
+            _ => None,
+        }
+    }
+
+    /// Sums the elements of a [`Self::List`] of ints and floats as `f64`.
+    ///
+    /// Returns `None` if this is not a list or any element is not a number. An empty list
+    /// sums to `0.0`.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let list = Value::from([Value::Int(1), Value::Int(2), Value::Float(3.5)]);
+    /// assert_eq!(list.sum_numbers(), Some(6.5));
+    /// assert_eq!(list.min_number(), Some(1.0));
+    /// assert_eq!(list.max_number(), Some(3.5));
+    /// ```
+    pub fn sum_numbers(&self) -> Option<f64> {
+        self.as_list()?
+            .iter()
+            .try_fold(0.0, |sum, item| Some(sum + item.as_number()?))
+    }
+
+    /// Returns the smallest element of a [`Self::List`] of ints and floats as `f64`.
+    ///
+    /// Returns `None` if this is not a list, the list is empty, or any element is not a
+    /// number. `NaN` elements are skipped, as by [`f64::min`].
+    pub fn min_number(&self) -> Option<f64> {
+        self.fold_numbers(f64::min)
+    }
+
+    /// Returns the largest element of a [`Self::List`] of ints and floats as `f64`; see
+    /// [`Self::min_number`].
+    pub fn max_number(&self) -> Option<f64> {
+        self.fold_numbers(f64::max)
+    }
+
+    fn fold_numbers(&self, pick: fn(f64, f64) -> f64) -> Option<f64> {
+        self.as_list()?
+            .iter()
+            .try_fold(None, |best: Option<f64>, item| {
+                let n = item.as_number()?;
+                Some(Some(best.map_or(n, |best| pick(best, n))))
+            })
+            .flatten()
+    }
+
+    /// Returns ints and floats as `f64`, otherwise `None`.
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Int(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Converts a [`Self::Map`] into its key-value pairs in key order, otherwise `None`.
+    pub fn into_pairs(self) -> Option<Vec<(String, Value)>> {
+        match self {
+            Value::Map(m) => Some(m.into_iter().collect()),
+            _ => None,
+        }
+    }
+
     /// Takes the value, leaving [`Self::Null`] in its place.
     pub fn take(&mut self) -> Value {
-        std::mem::replace(self, Value::Null)
+        core::mem::replace(self, Value::Null)
+    }
+
+    /// Compares two values structurally, treating `NaN` floats as equal to each other.
+    ///
+    /// This behaves like `==` except that `Float(NaN)` matches `Float(NaN)` at any depth,
+    /// which makes it suitable for round-trip checks.
+    pub fn structural_eq(&self, other: &Value) -> bool {
+        self.eq_with(other, false, &|a, b| a == b || (a.is_nan() && b.is_nan()))
+    }
+
+    /// Compares two values structurally, treating floats within `epsilon` of each other as equal.
+    ///
+    /// `NaN` matches `NaN`, and infinities only match infinities of the same sign.
+    pub fn approx_eq(&self, other: &Value, epsilon: f64) -> bool {
+        self.eq_with(other, false, &|a, b| {
+            a == b || (a.is_nan() && b.is_nan()) || (a - b).abs() <= epsilon
+        })
+    }
+
+    /// Compares two values by what they represent, treating `Int` and `Float` as one number type.
+    ///
+    /// `==` is type-strict: `Int(2) != Float(2.0)` because JASN keeps the two apart. This method
+    /// instead matches an integer with a float of exactly the same value, at any depth, which
+    /// helps when comparing data from sources that disagree on number types. No rounding is
+    /// involved, so integers beyond `f64` precision only match floats they equal exactly. Floats
+    /// compare with IEEE `==`, so `NaN` matches nothing; every other type must match exactly.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// assert_ne!(Value::Int(2), Value::Float(2.0));
+    /// assert!(Value::Int(2).value_eq(&Value::Float(2.0)));
+    /// assert!(!Value::Int(2).value_eq(&Value::Float(2.5)));
+    /// ```
+    pub fn value_eq(&self, other: &Value) -> bool {
+        self.eq_with(other, true, &|a, b| a == b)
+    }
+
+    /// Returns whether the value is truthy, following JavaScript-like rules.
+    ///
+    /// `null`, `false`, `0`, `0.0`, `NaN`, `""`, the zero duration, empty lists and empty maps
+    /// are falsy; every other value (including empty binary data and all timestamps) is truthy.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Int(i) => *i != 0,
+            Value::Float(f) => *f != 0.0 && !f.is_nan(),
+            Value::String(s) => !s.is_empty(),
+            Value::Binary(_) | Value::Timestamp(_) => true,
+            Value::Duration(d) => !d.is_zero(),
+            Value::List(list) => !list.is_empty(),
+            Value::Map(map) => !map.is_empty(),
+        }
+    }
+
+    /// Returns whether any integer in the tree falls outside JavaScript's safe-integer range.
+    ///
+    /// Integers beyond ±[`JS_MAX_SAFE_INTEGER`] (2^53 − 1) are stored exactly in
+    /// [`Value::Int`], but lose precision once read by a consumer that parses numbers as `f64`.
+    /// Map keys are strings and are not checked.
+    ///
+    /// ```
+    /// use jasn_core::{JS_MAX_SAFE_INTEGER, Value};
+    ///
+    /// assert!(!Value::from([JS_MAX_SAFE_INTEGER]).has_js_unsafe_integers());
+    /// assert!(Value::from([9007199254740993]).has_js_unsafe_integers());
+    /// ```
+    pub fn has_js_unsafe_integers(&self) -> bool {
+        match self {
+            Value::Int(i) => i.unsigned_abs() > JS_MAX_SAFE_INTEGER as u64,
+            Value::List(list) => list.iter().any(Value::has_js_unsafe_integers),
+            Value::Map(map) => map.values().any(Value::has_js_unsafe_integers),
+            _ => false,
+        }
+    }
+
+    /// Fills in keys from `defaults` that are missing in `self`, without overwriting anything.
+    ///
+    /// When both `self` and `defaults` are maps, keys absent from `self` are copied over and
+    /// keys present in both are merged recursively. Any other combination leaves `self`
+    /// unchanged, so a user-set scalar or list always wins over the default.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let mut config = Value::from([("port", 9000)]);
+    /// config.apply_defaults(&Value::from([("port", 80), ("workers", 4)]));
+    /// assert_eq!(config, Value::from([("port", 9000), ("workers", 4)]));
+    /// ```
+    pub fn apply_defaults(&mut self, defaults: &Value) {
+        let (Value::Map(map), Value::Map(defaults)) = (self, defaults) else {
+            return;
+        };
+        for (key, default) in defaults {
+            match map.get_mut(key) {
+                Some(value) => value.apply_defaults(default),
+                None => {
+                    map.insert(key.clone(), default.clone());
+                }
+            }
+        }
+    }
+
+    /// Recursively removes map entries whose value is [`Value::Null`].
+    ///
+    /// `null` list elements are kept, since their position is significant; use
+    /// [`Self::prune_all_nulls`] to drop those too. A `null` at the root is left untouched.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let mut value = Value::from([("a", Value::Null), ("b", Value::from([Value::Null]))]);
+    /// value.prune_nulls();
+    /// assert_eq!(value, Value::from([("b", Value::from([Value::Null]))]));
+    /// ```
+    pub fn prune_nulls(&mut self) {
+        self.prune_nulls_impl(false);
+    }
+
+    /// Recursively removes both `null` map entries and `null` list elements.
+    pub fn prune_all_nulls(&mut self) {
+        self.prune_nulls_impl(true);
+    }
+
+    fn prune_nulls_impl(&mut self, lists: bool) {
+        match self {
+            Value::List(list) => {
+                if lists {
+                    list.retain(|item| !item.is_null());
+                }
+                list.iter_mut()
+                    .for_each(|item| item.prune_nulls_impl(lists));
+            }
+            Value::Map(map) => {
+                map.retain(|_, item| !item.is_null());
+                map.values_mut()
+                    .for_each(|item| item.prune_nulls_impl(lists));
+            }
+            _ => {}
+        }
+    }
+
+    /// Renders the value as plain text for interpolation into templates.
+    ///
+    /// Strings are returned as-is without quotes, numbers and booleans as their literal text
+    /// (`inf`, `-inf` and `nan` for special floats), `null` as `null`, binary data as standard
+    /// base64 and timestamps as RFC 3339. Lists and maps fall back to the [`Display`] form.
+    ///
+    /// [`Display`]: core::fmt::Display
+    pub fn to_display_string(&self) -> String {
+        match self {
+            Value::Null => "null".to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Int(i) => i.to_string(),
+            Value::Float(f) if f.is_nan() => "nan".to_string(),
+            Value::Float(f) if f.is_infinite() => {
+                if f.is_sign_negative() { "-inf" } else { "inf" }.to_string()
+            }
+            Value::Float(f) => f.to_string(),
+            Value::String(s) => s.clone(),
+            Value::Binary(b) => b.to_string(),
+            Value::Timestamp(t) => timestamp::to_rfc3339(t),
+            Value::Duration(d) => duration::to_iso8601(d),
+            Value::List(_) | Value::Map(_) => self.to_string(),
+        }
+    }
+
+    /// Compares two trees with `float_eq` for pairs of floats; with `int_float`, an `Int` also
+    /// matches a `Float` of exactly the same value.
+    fn eq_with(
+        &self,
+        other: &Value,
+        int_float: bool,
+        float_eq: &impl Fn(f64, f64) -> bool,
+    ) -> bool {
+        match (self, other) {
+            (Value::Float(a), Value::Float(b)) => float_eq(*a, *b),
+            (Value::Int(i), Value::Float(f)) | (Value::Float(f), Value::Int(i)) if int_float => {
+                int_equals_float(*i, *f)
+            }
+            (Value::List(a), Value::List(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b)
+                        .all(|(a, b)| a.eq_with(b, int_float, float_eq))
+            }
+            (Value::Map(a), Value::Map(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(key, a)| {
+                        b.get(key)
+                            .is_some_and(|b| a.eq_with(b, int_float, float_eq))
+                    })
+            }
+            _ => self == other,
+        }
     }
 }
 
+/// Whether `f` is exactly the integer `i`, without the rounding of an `i as f64` cast.
+fn int_equals_float(i: i64, f: f64) -> bool {
+    // 2^63 is exactly representable, and every float in [-2^63, 2^63) truncates into an i64
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    (-LIMIT..LIMIT).contains(&f) && f as i64 == i && i as f64 == f
+}
+
 impl From<()> for Value {
     fn from(_: ()) -> Self {
         Value::Null
@@ -229,6 +723,12 @@ impl From<Timestamp> for Value {
     }
 }
 
+impl From<Duration> for Value {
+    fn from(value: Duration) -> Self {
+        Value::Duration(value)
+    }
+}
+
 impl<V> From<Vec<V>> for Value
 where
     V: Into<Value>,
@@ -366,12 +866,395 @@ impl PartialEq<bool> for Value {
     }
 }
 
+/// Error returned when a [`Value`] does not have the shape a [`TryFrom`] conversion expects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryFromValueError {
+    /// Type the conversion expected (`int`, `string`, `list`, ...).
+    pub expected: &'static str,
+    /// Type that was found instead.
+    pub got: &'static str,
+    /// Location of the mismatch inside nested lists and maps, such as `users[1].name`; empty
+    /// when the converted value itself has the wrong type.
+    pub path: String,
+}
+
+impl TryFromValueError {
+    fn new(expected: &'static str, got: &Value) -> Self {
+        Self {
+            expected,
+            got: schema::type_name(got),
+            path: String::new(),
+        }
+    }
+
+    /// Prefixes the path with a map key or list position.
+    fn within(mut self, index: Index<'_>) -> Self {
+        let separator = if self.path.is_empty() || self.path.starts_with('[') {
+            ""
+        } else {
+            "."
+        };
+        self.path = match index {
+            Index::Key(key) => alloc::format!("{key}{separator}{}", self.path),
+            Index::Position(i) => alloc::format!("[{i}]{separator}{}", self.path),
+        };
+        self
+    }
+}
+
+impl core::fmt::Display for TryFromValueError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "expected {}, got {}", self.expected, self.got)?;
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for TryFromValueError {}
+
+macro_rules! impl_try_from_value {
+    ($($variant:ident($ty:ty) => $name:literal),* $(,)?) => {
+        $(
+            impl TryFrom<Value> for $ty {
+                type Error = TryFromValueError;
+
+                fn try_from(value: Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::$variant(v) => Ok(v),
+                        other => Err(TryFromValueError::new($name, &other)),
+                    }
+                }
+            }
+
+            impl TryFrom<&Value> for $ty {
+                type Error = TryFromValueError;
+
+                fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::$variant(v) => Ok(v.clone()),
+                        other => Err(TryFromValueError::new($name, other)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_value! {
+    Bool(bool) => "bool",
+    Int(i64) => "int",
+    Float(f64) => "float",
+    String(String) => "string",
+    Binary(Binary) => "binary",
+    Timestamp(Timestamp) => "timestamp",
+    Duration(Duration) => "duration",
+}
+
+/// Converts each element with `T`'s own [`TryFrom`], failing on the first mismatch.
+impl<T> TryFrom<Value> for Vec<T>
+where
+    T: TryFrom<Value, Error = TryFromValueError>,
+{
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::List(list) => list
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| T::try_from(item).map_err(|e| e.within(Index::Position(i))))
+                .collect(),
+            other => Err(TryFromValueError::new("list", &other)),
+        }
+    }
+}
+
+/// Converts each map value with `T`'s own [`TryFrom`], failing on the first mismatch.
+impl<T> TryFrom<Value> for BTreeMap<String, T>
+where
+    T: TryFrom<Value, Error = TryFromValueError>,
+{
+    type Error = TryFromValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => map
+                .into_iter()
+                .map(|(key, item)| match T::try_from(item) {
+                    Ok(item) => Ok((key, item)),
+                    Err(e) => Err(e.within(Index::Key(&key))),
+                })
+                .collect(),
+            other => Err(TryFromValueError::new("map", &other)),
+        }
+    }
+}
+
+/// Borrowing form of the `Vec<T>` conversion, see [`Value::as_array_of`].
+impl<T> TryFrom<&Value> for Vec<T>
+where
+    T: for<'a> TryFrom<&'a Value, Error = TryFromValueError>,
+{
+    type Error = TryFromValueError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.as_array_of()
+    }
+}
+
+/// Borrowing form of the `BTreeMap<String, T>` conversion; keys and scalars are cloned.
+impl<T> TryFrom<&Value> for BTreeMap<String, T>
+where
+    T: for<'a> TryFrom<&'a Value, Error = TryFromValueError>,
+{
+    type Error = TryFromValueError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => map
+                .iter()
+                .map(|(key, item)| match T::try_from(item) {
+                    Ok(item) => Ok((key.clone(), item)),
+                    Err(e) => Err(e.within(Index::Key(key))),
+                })
+                .collect(),
+            other => Err(TryFromValueError::new("map", other)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
 
     use super::*;
 
+    #[test]
+    fn test_try_from_collections() {
+        let value = Value::from([("a", 1), ("b", 2)]);
+        let map: BTreeMap<String, i64> = value.try_into().unwrap();
+        assert_eq!(
+            map,
+            BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)])
+        );
+
+        let value = Value::from(["x", "y"]);
+        let list: Vec<String> = value.try_into().unwrap();
+        assert_eq!(list, ["x", "y"]);
+
+        let value = Value::from([(
+            "rows",
+            Value::from([Value::from([1.5]), Value::List(vec![])]),
+        )]);
+        let nested: BTreeMap<String, Vec<Vec<f64>>> = value.try_into().unwrap();
+        assert_eq!(nested["rows"], [vec![1.5], vec![]]);
+    }
+
+    #[test]
+    fn test_as_array_of() {
+        let value = Value::from([1, 2, 3]);
+        assert_eq!(value.as_array_of::<i64>().unwrap(), [1, 2, 3]);
+        assert_eq!(value, Value::from([1, 2, 3]));
+
+        let value = Value::from(["a", "b"]);
+        assert_eq!(value.as_array_of::<String>().unwrap(), ["a", "b"]);
+
+        assert_eq!(Value::List(vec![]).as_array_of::<bool>().unwrap(), []);
+
+        let nested = Value::from([Value::from([1.5]), Value::List(vec![])]);
+        assert_eq!(
+            nested.as_array_of::<Vec<f64>>().unwrap(),
+            [vec![1.5], vec![]]
+        );
+    }
+
+    #[test]
+    fn test_try_from_borrowed() {
+        let value = Value::from([("a", Value::from([1, 2])), ("b", Value::from([3]))]);
+        let map = BTreeMap::<String, Vec<i64>>::try_from(&value).unwrap();
+        assert_eq!(map["a"], [1, 2]);
+        assert_eq!(
+            map,
+            BTreeMap::<String, Vec<i64>>::try_from(value.clone()).unwrap()
+        );
+        assert_eq!(String::try_from(&Value::from("x")).unwrap(), "x");
+        assert_eq!(
+            i64::try_from(&Value::Null).unwrap_err().to_string(),
+            "expected int, got null"
+        );
+
+        let nested = Value::from([("a", Value::from([Value::from("x")]))]);
+        let err = BTreeMap::<String, Vec<bool>>::try_from(&nested).unwrap_err();
+        assert_eq!(err.to_string(), "expected bool, got string at a[0]");
+    }
+
+    #[rstest]
+    #[case(Value::from([Value::Int(1), Value::from("2")]), "expected int, got string at [1]")]
+    #[case(Value::from([Value::Int(1), Value::Float(2.0)]), "expected int, got float at [1]")]
+    #[case(Value::Int(1), "expected list, got int")]
+    fn test_as_array_of_mismatch(#[case] value: Value, #[case] expected: &str) {
+        let err = value.as_array_of::<i64>().unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(Value::from([Value::from("x"), Value::Int(2)]), "expected string, got int at [1]")]
+    #[case(Value::from([("a", 1)]), "expected list, got map")]
+    #[case(Value::from([Value::from(["x"])]), "expected string, got list at [0]")]
+    fn test_try_from_list_mismatch(#[case] value: Value, #[case] expected: &str) {
+        let err = Vec::<String>::try_from(value).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn test_try_from_map_mismatch() {
+        let value = Value::from([("users", Value::from([Value::from([("age", "x")])]))]);
+        let err = BTreeMap::<String, Vec<BTreeMap<String, i64>>>::try_from(value).unwrap_err();
+        assert_eq!(
+            err,
+            TryFromValueError {
+                expected: "int",
+                got: "string",
+                path: "users[0].age".to_string(),
+            }
+        );
+        assert_eq!(err.to_string(), "expected int, got string at users[0].age");
+
+        assert_eq!(
+            i64::try_from(Value::Float(1.0)).unwrap_err().to_string(),
+            "expected int, got float"
+        );
+    }
+
+    #[test]
+    fn test_try_from_nested_list_path() {
+        // Nested list positions chain without a separator
+        let grid = Value::from([Value::from([Value::Null])]);
+        assert_eq!(Vec::<Vec<bool>>::try_from(grid).unwrap_err().path, "[0][0]");
+    }
+
+    #[test]
+    fn test_apply_defaults() {
+        let defaults = Value::from([
+            (
+                "server",
+                Value::from([
+                    ("host", Value::from("localhost")),
+                    ("port", Value::Int(80)),
+                    ("tls", Value::from([("enabled", false)])),
+                ]),
+            ),
+            ("features", Value::from(["a", "b"])),
+            ("log", Value::from([("level", "info")])),
+        ]);
+        let mut config = Value::from([
+            (
+                "server",
+                Value::from([
+                    ("port", Value::Int(8080)),
+                    ("tls", Value::from([("cert", "x.pem")])),
+                ]),
+            ),
+            ("features", Value::from(["c"])),
+            ("log", Value::Null),
+        ]);
+
+        config.apply_defaults(&defaults);
+        assert_eq!(
+            config,
+            Value::from([
+                (
+                    "server",
+                    Value::from([
+                        ("host", Value::from("localhost")),
+                        ("port", Value::Int(8080)),
+                        (
+                            "tls",
+                            Value::from([
+                                ("cert", Value::from("x.pem")),
+                                ("enabled", Value::Bool(false))
+                            ])
+                        ),
+                    ]),
+                ),
+                ("features", Value::from(["c"])),
+                ("log", Value::Null),
+            ])
+        );
+
+        let mut scalar = Value::Int(1);
+        scalar.apply_defaults(&defaults);
+        assert_eq!(scalar, Value::Int(1));
+    }
+
+    fn with_nulls() -> Value {
+        Value::from([
+            ("keep", Value::Int(1)),
+            ("drop", Value::Null),
+            (
+                "nested",
+                Value::from([
+                    ("drop", Value::Null),
+                    (
+                        "list",
+                        Value::from([Value::Null, Value::from([("x", Value::Null)])]),
+                    ),
+                ]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_prune_nulls() {
+        let mut value = with_nulls();
+        value.prune_nulls();
+        assert_eq!(
+            value,
+            Value::from([
+                ("keep", Value::Int(1)),
+                (
+                    "nested",
+                    Value::from([(
+                        "list",
+                        Value::from([Value::Null, Value::Map(BTreeMap::new())])
+                    )]),
+                ),
+            ])
+        );
+
+        let mut value = with_nulls();
+        value.prune_all_nulls();
+        assert_eq!(
+            value,
+            Value::from([
+                ("keep", Value::Int(1)),
+                (
+                    "nested",
+                    Value::from([("list", Value::from([Value::Map(BTreeMap::new())]))]),
+                ),
+            ])
+        );
+
+        let mut value = Value::Null;
+        value.prune_all_nulls();
+        assert_eq!(value, Value::Null);
+    }
+
+    #[rstest]
+    #[case(Value::Int(JS_MAX_SAFE_INTEGER), false)]
+    #[case(Value::Int(-JS_MAX_SAFE_INTEGER), false)]
+    #[case(Value::Int(JS_MAX_SAFE_INTEGER + 1), true)]
+    #[case(Value::Int(-JS_MAX_SAFE_INTEGER - 1), true)]
+    #[case(Value::Int(i64::MIN), true)]
+    #[case(Value::Float(1e300), false)]
+    #[case(Value::from([Value::Int(1), Value::from([("big", 9007199254740993)])]), true)]
+    #[case(Value::from([("a", [1, 2, 3])]), false)]
+    fn test_has_js_unsafe_integers(#[case] value: Value, #[case] expected: bool) {
+        assert_eq!(value.has_js_unsafe_integers(), expected);
+    }
+
     #[rstest]
     #[case(Value::Null, "null")]
     #[case(Value::Bool(true), "bool")]
@@ -380,6 +1263,7 @@ mod tests {
     #[case(Value::String("hello".to_string()), "string")]
     #[case(Value::Binary(Binary(vec![1, 2, 3])), "binary")]
     #[case(Value::Timestamp(Timestamp::from_unix_timestamp(1234567890).unwrap()), "timestamp")]
+    #[case(Value::Duration(Duration::from_secs(90)), "duration")]
     #[case(Value::List(vec![Value::Null]), "list")]
     #[case(Value::Map(BTreeMap::new()), "map")]
     fn test_is_methods(#[case] value: Value, #[case] value_type: &str) {
@@ -390,6 +1274,7 @@ mod tests {
         assert_eq!(value.is_string(), value_type == "string");
         assert_eq!(value.is_binary(), value_type == "binary");
         assert_eq!(value.is_timestamp(), value_type == "timestamp");
+        assert_eq!(value.is_duration(), value_type == "duration");
         assert_eq!(value.is_list(), value_type == "list");
         assert_eq!(value.is_map(), value_type == "map");
     }
@@ -429,6 +1314,15 @@ mod tests {
         assert_eq!(Value::Null.as_binary(), None);
     }
 
+    #[rstest]
+    #[case(Value::String("héllo".to_string()), Some("héllo".as_bytes()))]
+    #[case(Value::Binary(Binary(vec![0, 255])), Some(&[0u8, 255][..]))]
+    #[case(Value::Int(42), None)]
+    #[case(Value::Null, None)]
+    fn test_as_bytes(#[case] value: Value, #[case] expected: Option<&[u8]>) {
+        assert_eq!(value.as_bytes(), expected);
+    }
+
     #[test]
     fn test_as_timestamp() {
         let ts = Timestamp::from_unix_timestamp(1234567890).unwrap();
@@ -454,6 +1348,52 @@ mod tests {
         assert_eq!(Value::Null.as_map(), None);
     }
 
+    #[rstest]
+    #[case(Value::from([Value::Int(1), Value::Int(2), Value::Float(3.5)]), Some(6.5), Some(1.0), Some(3.5))]
+    #[case(Value::from([-4, 10]), Some(6.0), Some(-4.0), Some(10.0))]
+    #[case(Value::List(vec![]), Some(0.0), None, None)]
+    #[case(Value::from([Value::Int(1), Value::from("2")]), None, None, None)]
+    #[case(Value::from([Value::Int(1), Value::Null]), None, None, None)]
+    #[case(Value::Int(5), None, None, None)]
+    #[case(Value::from([("a", 1)]), None, None, None)]
+    fn test_number_aggregates(
+        #[case] value: Value,
+        #[case] sum: Option<f64>,
+        #[case] min: Option<f64>,
+        #[case] max: Option<f64>,
+    ) {
+        assert_eq!(value.sum_numbers(), sum);
+        assert_eq!(value.min_number(), min);
+        assert_eq!(value.max_number(), max);
+    }
+
+    #[test]
+    fn test_number_aggregates_nan() {
+        let value = Value::from([Value::Float(f64::NAN), Value::Int(2)]);
+        assert!(value.sum_numbers().unwrap().is_nan());
+        assert_eq!(value.min_number(), Some(2.0));
+        assert_eq!(value.max_number(), Some(2.0));
+    }
+
+    #[test]
+    fn test_map_iterators() {
+        let value = Value::from([("b", 2), ("a", 1), ("c", 3)]);
+        assert_eq!(value.map_entries(), value.as_map());
+        assert_eq!(
+            value.map_keys().unwrap().collect::<Vec<_>>(),
+            ["a", "b", "c"]
+        );
+        assert_eq!(
+            value.map_values().unwrap().collect::<Vec<_>>(),
+            [&Value::Int(1), &Value::Int(2), &Value::Int(3)]
+        );
+
+        let list = Value::from([1, 2]);
+        assert!(list.map_entries().is_none());
+        assert!(list.map_keys().is_none());
+        assert!(list.map_values().is_none());
+    }
+
     #[rstest]
     #[case(Value::from(()), Value::Null)]
     #[case(Value::from(true), Value::Bool(true))]
@@ -483,6 +1423,10 @@ mod tests {
         let dt = Timestamp::from_unix_timestamp(1234567890).unwrap();
         assert_eq!(Value::from(dt), Value::Timestamp(dt));
 
+        // From Duration
+        let d = Duration::from_millis(1500);
+        assert_eq!(Value::from(d), Value::Duration(d));
+
         // Value::Binary from byte literal
         let value = Value::Binary(b"data".into());
         assert_eq!(value, Value::Binary(Binary(b"data".to_vec())));
@@ -617,6 +1561,70 @@ mod tests {
         assert_eq!(null, Value::Null);
     }
 
+    #[test]
+    fn test_structural_eq() {
+        let a = Value::from([
+            ("nan", Value::Float(f64::NAN)),
+            ("list", Value::from([Value::Float(f64::NAN), Value::Int(1)])),
+        ]);
+        let b = a.clone();
+        assert_ne!(a, b);
+        assert!(a.structural_eq(&b));
+
+        let c = Value::from([
+            ("nan", Value::Float(f64::NAN)),
+            ("list", Value::from([Value::Float(f64::NAN), Value::Int(2)])),
+        ]);
+        assert!(!a.structural_eq(&c));
+
+        // Types are not coerced
+        assert!(!Value::Int(1).structural_eq(&Value::Float(1.0)));
+        assert!(!Value::Float(f64::NAN).structural_eq(&Value::Null));
+    }
+
+    #[rstest]
+    #[case(Value::Int(2), Value::Float(2.0), true)]
+    #[case(Value::Int(-3), Value::Float(-3.0), true)]
+    #[case(Value::Int(0), Value::Float(-0.0), true)]
+    #[case(Value::Int(2), Value::Float(2.5), false)]
+    #[case(Value::Int(2), Value::Int(3), false)]
+    #[case(Value::Int(i64::MIN), Value::Float(i64::MIN as f64), true)]
+    #[case(Value::Int(i64::MAX), Value::Float(i64::MAX as f64), false)]
+    #[case(Value::Int((1 << 53) + 1), Value::Float((1u64 << 53) as f64), false)]
+    #[case(Value::Int(0), Value::Float(f64::NAN), false)]
+    #[case(Value::Float(f64::NAN), Value::Float(f64::NAN), false)]
+    #[case(Value::Int(1), Value::Bool(true), false)]
+    #[case(Value::Int(1), Value::from("1"), false)]
+    #[case(Value::from([1, 2]), Value::from([1.0, 2.0]), true)]
+    #[case(Value::from([("x", 1)]), Value::from([("x", 1.0)]), true)]
+    #[case(Value::from([("x", 1)]), Value::from([("y", 1.0)]), false)]
+    #[case(Value::from([1]), Value::from([1.0, 1.0]), false)]
+    fn test_value_eq(#[case] a: Value, #[case] b: Value, #[case] expected: bool) {
+        assert_eq!(a.value_eq(&b), expected);
+        assert_eq!(b.value_eq(&a), expected);
+    }
+
+    #[test]
+    fn test_value_eq_keeps_partial_eq_strict() {
+        assert!(Value::Int(2).value_eq(&Value::Float(2.0)));
+        assert_ne!(Value::Int(2), Value::Float(2.0));
+    }
+
+    #[rstest]
+    #[case(Value::Float(0.1 + 0.2), Value::Float(0.3), true)]
+    #[case(Value::Float(1.0), Value::Float(1.1), false)]
+    #[case(Value::Float(f64::NAN), Value::Float(f64::NAN), true)]
+    #[case(Value::Float(f64::INFINITY), Value::Float(f64::INFINITY), true)]
+    #[case(Value::Float(f64::INFINITY), Value::Float(f64::NEG_INFINITY), false)]
+    #[case(Value::from([0.1 + 0.2, 1.0]), Value::from([0.3, 1.0]), true)]
+    #[case(Value::from([("x", 0.1 + 0.2)]), Value::from([("x", 0.3)]), true)]
+    #[case(Value::from([("x", 0.3)]), Value::from([("y", 0.3)]), false)]
+    #[case(Value::from([0.3]), Value::from([0.3, 0.3]), false)]
+    fn test_approx_eq(#[case] a: Value, #[case] b: Value, #[case] expected: bool) {
+        assert_eq!(a.approx_eq(&b, 1e-9), expected);
+        assert_eq!(b.approx_eq(&a, 1e-9), expected);
+    }
+
     #[test]
     fn test_partial_eq_string() {
         let string_val = Value::String("hello".to_string());
@@ -653,4 +1661,186 @@ mod tests {
         assert_ne!(int_val, "42");
         assert_ne!(string_val, 42i64);
     }
+
+    #[rstest]
+    #[case(Value::Null, false)]
+    #[case(Value::Bool(false), false)]
+    #[case(Value::Bool(true), true)]
+    #[case(Value::Int(0), false)]
+    #[case(Value::Int(-1), true)]
+    #[case(Value::Float(0.0), false)]
+    #[case(Value::Float(-0.0), false)]
+    #[case(Value::Float(f64::NAN), false)]
+    #[case(Value::Float(0.5), true)]
+    #[case(Value::Float(f64::NEG_INFINITY), true)]
+    #[case(Value::from(""), false)]
+    #[case(Value::from("0"), true)]
+    #[case(Value::from(" "), true)]
+    #[case(Value::Binary(Binary::new()), true)]
+    #[case(Value::Timestamp(Timestamp::UNIX_EPOCH), true)]
+    #[case(Value::Duration(Duration::ZERO), false)]
+    #[case(Value::Duration(Duration::from_nanos(1)), true)]
+    #[case(Value::List(vec![]), false)]
+    #[case(Value::List(vec![Value::Null]), true)]
+    #[case(Value::Map(BTreeMap::new()), false)]
+    #[case(Value::from([("a", Value::Null)]), true)]
+    fn test_is_truthy(#[case] value: Value, #[case] expected: bool) {
+        assert_eq!(value.is_truthy(), expected);
+    }
+
+    #[rstest]
+    #[case(Value::Null, "null")]
+    #[case(Value::Bool(true), "true")]
+    #[case(Value::Bool(false), "false")]
+    #[case(Value::Int(-42), "-42")]
+    #[case(Value::Float(2.5), "2.5")]
+    #[case(Value::Float(f64::INFINITY), "inf")]
+    #[case(Value::Float(f64::NEG_INFINITY), "-inf")]
+    #[case(Value::Float(f64::NAN), "nan")]
+    #[case(Value::from("say \"hi\""), "say \"hi\"")]
+    #[case(Value::Binary(Binary(b"Hello".to_vec())), "SGVsbG8=")]
+    #[case(Value::Timestamp(Timestamp::UNIX_EPOCH), "1970-01-01T00:00:00Z")]
+    #[case(
+        Value::Timestamp(time::macros::datetime!(2009-02-13 23:31:30.1234 -05:30)),
+        "2009-02-13T23:31:30.1234-05:30"
+    )]
+    #[case(
+        Value::Timestamp(time::macros::datetime!(2024-01-01 0:00 +01:00:30)),
+        "2024-01-01 0:00:00.0 +01:00:30"
+    )]
+    #[case(Value::Duration(Duration::from_secs(5400)), "PT1H30M")]
+    fn test_to_display_string(#[case] value: Value, #[case] expected: &str) {
+        assert_eq!(value.to_display_string(), expected);
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut value = Value::from([
+            ("name", Value::from("a")),
+            ("ports", Value::from([80, 443])),
+        ]);
+
+        *value.get_mut("name").unwrap() = Value::from("b");
+        if let Some(port) = value.get_mut("ports").and_then(|ports| ports.get_mut(1)) {
+            *port = Value::Int(8443);
+        }
+        assert_eq!(
+            value,
+            Value::from([
+                ("name", Value::from("b")),
+                ("ports", Value::from([80, 8443]))
+            ])
+        );
+
+        let key = String::from("ports");
+        assert_eq!(
+            value.get(&key).and_then(|ports| ports.get(0)),
+            Some(&Value::Int(80))
+        );
+        assert!(value.get_mut("ports").unwrap().get_mut(2).is_none());
+        assert!(value.get_mut("missing").is_none());
+        assert!(value.get_mut(0).is_none());
+        assert!(value.get_mut("name").unwrap().get_mut("x").is_none());
+        assert!(value.get_mut("ports").unwrap().get_mut("x").is_none());
+    }
+
+    #[test]
+    fn test_entry_path() {
+        let mut value = Value::Map(BTreeMap::new());
+        *value.entry_path("server.host").unwrap() = Value::from("localhost");
+        *value.entry_path("server.ports").unwrap() = Value::from([80, 443]);
+        *value.entry_path("server.tls.enabled").unwrap() = Value::Bool(true);
+        *value.entry_path("debug").unwrap() = Value::Bool(false);
+        assert!(value.entry_path("server.tls.cert").unwrap().is_null());
+
+        assert_eq!(
+            value,
+            Value::from([
+                (
+                    "server",
+                    Value::from([
+                        ("host", Value::from("localhost")),
+                        ("ports", Value::from([80, 443])),
+                        (
+                            "tls",
+                            Value::from([("enabled", Value::Bool(true)), ("cert", Value::Null)])
+                        ),
+                    ])
+                ),
+                ("debug", Value::Bool(false)),
+            ])
+        );
+
+        // Existing values are returned as they are, and nulls along the path become maps
+        assert_eq!(
+            value.entry_path("server.ports").unwrap().get(1),
+            Some(&Value::Int(443))
+        );
+        *value.entry_path("server.tls.cert.path").unwrap() = Value::from("cert.pem");
+        assert_eq!(
+            value.pointer("/server/tls/cert/path"),
+            Some(&Value::from("cert.pem"))
+        );
+
+        // The empty path is the value itself, and a null root becomes a map
+        let mut scalar = Value::Int(1);
+        assert_eq!(scalar.entry_path(""), Some(&mut Value::Int(1)));
+        let mut null = Value::Null;
+        *null.entry_path("a").unwrap() = Value::Int(2);
+        assert_eq!(null, Value::from([("a", 2)]));
+    }
+
+    #[rstest]
+    #[case("server.ports.http")]
+    #[case("server.ports.0")]
+    #[case("debug.level")]
+    #[case("debug.level.name")]
+    fn test_entry_path_through_non_map(#[case] path: &str) {
+        let mut value = Value::from([
+            ("server", Value::from([("ports", Value::from([80, 443]))])),
+            ("debug", Value::Bool(false)),
+        ]);
+        let before = value.clone();
+        assert_eq!(value.entry_path(path), None);
+        assert_eq!(value, before);
+
+        let mut scalar = Value::Int(1);
+        assert_eq!(scalar.entry_path(path), None);
+        assert_eq!(scalar, Value::Int(1));
+    }
+
+    #[test]
+    fn test_into_pairs() {
+        let value = Value::from([("b", 2), ("a", 1)]);
+        assert_eq!(
+            value.into_pairs(),
+            Some(vec![
+                ("a".to_string(), Value::Int(1)),
+                ("b".to_string(), Value::Int(2))
+            ])
+        );
+        assert_eq!(Value::Map(BTreeMap::new()).into_pairs(), Some(vec![]));
+        assert_eq!(Value::from([1, 2]).into_pairs(), None);
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Value::bool(true), Value::Bool(true));
+        assert_eq!(Value::int(42), Value::Int(42));
+        assert_eq!(Value::float(2.5), Value::Float(2.5));
+        assert_eq!(Value::string("x"), Value::String("x".to_string()));
+        assert_eq!(
+            Value::string(String::from("y")),
+            Value::String("y".to_string())
+        );
+        assert_eq!(
+            Value::binary(vec![1u8, 2]),
+            Value::Binary(Binary(vec![1, 2]))
+        );
+        assert_eq!(Value::binary(b"ab"), Value::Binary(Binary(b"ab".to_vec())));
+        assert_eq!(
+            Value::timestamp(Timestamp::UNIX_EPOCH),
+            Value::Timestamp(Timestamp::UNIX_EPOCH)
+        );
+    }
 }
@@ -1,8 +1,12 @@
-use std::{
+use alloc::vec::Vec;
+use core::{
     borrow::Borrow,
+    fmt,
     ops::{Deref, DerefMut},
 };
 
+use base64::{Engine, prelude::BASE64_STANDARD};
+
 /// A wrapper for [`Vec<u8>`]... at least until Rust supports specialization.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct Binary(pub Vec<u8>);
@@ -62,6 +66,47 @@ impl Binary {
     pub fn extend_from_slice(&mut self, slice: &[u8]) {
         self.0.extend_from_slice(slice);
     }
+
+    /// Returns `true` if `hex` is a hex encoding of exactly these bytes, in either letter case.
+    ///
+    /// Text that is not valid hex (an odd number of digits, or any other character, including
+    /// whitespace) never matches.
+    ///
+    /// ```
+    /// use jasn_core::Binary;
+    ///
+    /// let binary = Binary::from([0xde, 0xad, 0xbe, 0xef]);
+    /// assert!(binary.eq_hex("deadbeef"));
+    /// assert!(binary.eq_hex("DEADBEEF"));
+    /// assert!(!binary.eq_hex("deadbe"));
+    /// ```
+    pub fn eq_hex(&self, hex: &str) -> bool {
+        let digits = hex.as_bytes();
+        digits.len() == self.0.len() * 2
+            && digits.chunks(2).zip(&self.0).all(|(pair, &byte)| {
+                let high = (pair[0] as char).to_digit(16);
+                let low = (pair[1] as char).to_digit(16);
+                matches!((high, low), (Some(h), Some(l)) if h * 16 + l == u32::from(byte))
+            })
+    }
+
+    /// Returns `true` if `b64` is the standard padded base64 encoding of exactly these bytes,
+    /// as written by [`Display`](fmt::Display).
+    ///
+    /// Text that is not valid base64 never matches.
+    ///
+    /// ```
+    /// use jasn_core::Binary;
+    ///
+    /// let binary = Binary::from(b"hello");
+    /// assert!(binary.eq_base64("aGVsbG8="));
+    /// assert!(!binary.eq_base64("aGVsbG8h"));
+    /// ```
+    pub fn eq_base64(&self, b64: &str) -> bool {
+        BASE64_STANDARD
+            .decode(b64)
+            .is_ok_and(|decoded| decoded == self.0)
+    }
 }
 
 impl From<Vec<u8>> for Binary {
@@ -94,6 +139,30 @@ impl<const N: usize> From<[u8; N]> for Binary {
     }
 }
 
+/// Takes over the buffer without copying when `bytes` is the unique owner of a `Vec`-backed
+/// allocation, and copies otherwise.
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for Binary {
+    fn from(bytes: bytes::Bytes) -> Self {
+        Binary(Vec::from(bytes))
+    }
+}
+
+/// Hands the buffer over to [`bytes::Bytes`] without copying.
+#[cfg(feature = "bytes")]
+impl From<Binary> for bytes::Bytes {
+    fn from(binary: Binary) -> Self {
+        bytes::Bytes::from(binary.0)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for super::Value {
+    fn from(bytes: bytes::Bytes) -> Self {
+        super::Value::Binary(bytes.into())
+    }
+}
+
 impl AsRef<[u8]> for Binary {
     fn as_ref(&self) -> &[u8] {
         &self.0
@@ -156,6 +225,27 @@ impl<const N: usize> PartialEq<&[u8; N]> for Binary {
     }
 }
 
+/// Formats the bytes as standard padded base64, without the `b64"..."` wrapper.
+impl fmt::Display for Binary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&BASE64_STANDARD.encode(&self.0))
+    }
+}
+
+/// Formats the bytes as lowercase hex digits, without the `hex"..."` wrapper.
+impl fmt::LowerHex for Binary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+/// Formats the bytes as uppercase hex digits, without the `hex"..."` wrapper.
+impl fmt::UpperHex for Binary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02X}", byte))
+    }
+}
+
 impl FromIterator<u8> for Binary {
     fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
         Binary(iter.into_iter().collect())
@@ -170,7 +260,7 @@ impl Extend<u8> for Binary {
 
 impl IntoIterator for Binary {
     type Item = u8;
-    type IntoIter = std::vec::IntoIter<u8>;
+    type IntoIter = alloc::vec::IntoIter<u8>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.into_iter()
@@ -179,7 +269,7 @@ impl IntoIterator for Binary {
 
 impl<'a> IntoIterator for &'a Binary {
     type Item = &'a u8;
-    type IntoIter = std::slice::Iter<'a, u8>;
+    type IntoIter = core::slice::Iter<'a, u8>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.iter()
@@ -188,7 +278,7 @@ impl<'a> IntoIterator for &'a Binary {
 
 impl<'a> IntoIterator for &'a mut Binary {
     type Item = &'a mut u8;
-    type IntoIter = std::slice::IterMut<'a, u8>;
+    type IntoIter = core::slice::IterMut<'a, u8>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.iter_mut()
@@ -210,6 +300,40 @@ mod tests {
         assert_eq!(binary, Binary(expected));
     }
 
+    #[rstest]
+    #[case(&[0xde, 0xad, 0xbe, 0xef], "deadbeef", true)]
+    #[case(&[0xde, 0xad, 0xbe, 0xef], "DeadBeef", true)]
+    #[case(&[0x00, 0x0f], "000f", true)]
+    #[case(&[], "", true)]
+    #[case(&[0xde, 0xad, 0xbe, 0xef], "deadbeee", false)]
+    #[case(&[0xde, 0xad, 0xbe, 0xef], "deadbe", false)]
+    #[case(&[0xde, 0xad, 0xbe, 0xef], "deadbeef00", false)]
+    #[case(&[0xde, 0xad], "dea", false)]
+    #[case(&[0xde, 0xad], "de ad", false)]
+    #[case(&[0xde, 0xad], "0xdead", false)]
+    #[case(&[0x00], "0g", false)]
+    #[case(&[0xe9], "é", false)]
+    fn test_eq_hex(#[case] bytes: &[u8], #[case] hex: &str, #[case] expected: bool) {
+        assert_eq!(Binary::from(bytes).eq_hex(hex), expected);
+    }
+
+    #[rstest]
+    #[case(b"hello", "aGVsbG8=", true)]
+    #[case(b"", "", true)]
+    #[case(&[0xfb, 0xff], "+/8=", true)]
+    #[case(b"hello", "aGVsbG8h", false)]
+    #[case(b"hello", "aGVsbG8", false)]
+    #[case(b"hello", "aGVsbA==", false)]
+    #[case(b"hello", "aGVs bG8=", false)]
+    #[case(&[0xfb, 0xff], "-_8=", false)]
+    fn test_eq_base64(#[case] bytes: &[u8], #[case] b64: &str, #[case] expected: bool) {
+        let binary = Binary::from(bytes);
+        assert_eq!(binary.eq_base64(b64), expected);
+        if expected {
+            assert_eq!(binary.to_string(), b64);
+        }
+    }
+
     #[test]
     fn test_binary_conversions() {
         // Binary from &[u8] (slice reference)
@@ -245,6 +369,37 @@ mod tests {
         assert_eq!(&*binary, &[4, 88, 6]);
     }
 
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn test_bytes_conversions() {
+        use bytes::Bytes;
+
+        use crate::Value;
+
+        let binary = Binary::from(Bytes::from_static(b"static"));
+        assert_eq!(binary, b"static");
+
+        // A uniquely owned Vec-backed buffer moves in both directions
+        let data = vec![1u8, 2, 3];
+        let ptr = data.as_ptr();
+        let bytes = Bytes::from(Binary(data));
+        assert_eq!(bytes.as_ptr(), ptr);
+        let binary = Binary::from(bytes);
+        assert_eq!(binary.as_ptr(), ptr);
+        assert_eq!(binary, [1u8, 2, 3]);
+
+        // Shared buffers are copied
+        let bytes = Bytes::from(vec![4u8, 5, 6]);
+        let binary = Binary::from(bytes.clone());
+        assert_eq!(binary, [4u8, 5, 6]);
+        assert_eq!(bytes, [4u8, 5, 6].as_slice());
+
+        assert_eq!(
+            Value::from(Bytes::from_static(b"hi")),
+            Value::Binary(Binary::from(b"hi"))
+        );
+    }
+
     #[test]
     fn test_binary_construction() {
         // Binary::new()
@@ -391,4 +546,20 @@ mod tests {
         map.insert(key2.clone(), "world");
         assert_eq!(map.get(b"hello".as_slice()), Some(&"world"));
     }
+
+    #[rstest]
+    #[case(Binary::from(b"Hello"), "48656c6c6f", "48656C6C6F", "SGVsbG8=")]
+    #[case(Binary::from([0x00u8, 0x0f, 0xab, 0xff]), "000fabff", "000FABFF", "AA+r/w==")]
+    #[case(Binary::new(), "", "", "")]
+    fn test_binary_formatting(
+        #[case] binary: Binary,
+        #[case] lower: &str,
+        #[case] upper: &str,
+        #[case] base64: &str,
+    ) {
+        assert_eq!(format!("{:x}", binary), lower);
+        assert_eq!(format!("{:X}", binary), upper);
+        assert_eq!(format!("{}", binary), base64);
+        assert_eq!(binary.to_string(), base64);
+    }
 }
@@ -0,0 +1,124 @@
+use super::Value;
+
+/// Number of values of each type in a tree, as returned by [`Value::count_by_type`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypeCounts {
+    /// Number of `null` values.
+    pub null: usize,
+    /// Number of booleans.
+    pub bool: usize,
+    /// Number of integers.
+    pub int: usize,
+    /// Number of floats.
+    pub float: usize,
+    /// Number of strings (map keys are not counted).
+    pub string: usize,
+    /// Number of binary values.
+    pub binary: usize,
+    /// Number of timestamps.
+    pub timestamp: usize,
+    /// Number of durations.
+    pub duration: usize,
+    /// Number of lists.
+    pub list: usize,
+    /// Number of maps.
+    pub map: usize,
+}
+
+impl TypeCounts {
+    /// Total number of values across all types.
+    pub fn total(&self) -> usize {
+        self.null
+            + self.bool
+            + self.int
+            + self.float
+            + self.string
+            + self.binary
+            + self.timestamp
+            + self.duration
+            + self.list
+            + self.map
+    }
+}
+
+impl Value {
+    /// Tallies every value in the tree by type, including `self` and all nested items.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let counts = Value::from([("a", Value::from([1, 2])), ("b", Value::Null)]).count_by_type();
+    /// assert_eq!((counts.map, counts.list, counts.int, counts.null), (1, 1, 2, 1));
+    /// ```
+    pub fn count_by_type(&self) -> TypeCounts {
+        let mut counts = TypeCounts::default();
+        count_into(self, &mut counts);
+        counts
+    }
+}
+
+fn count_into(value: &Value, counts: &mut TypeCounts) {
+    match value {
+        Value::Null => counts.null += 1,
+        Value::Bool(_) => counts.bool += 1,
+        Value::Int(_) => counts.int += 1,
+        Value::Float(_) => counts.float += 1,
+        Value::String(_) => counts.string += 1,
+        Value::Binary(_) => counts.binary += 1,
+        Value::Timestamp(_) => counts.timestamp += 1,
+        Value::Duration(_) => counts.duration += 1,
+        Value::List(list) => {
+            counts.list += 1;
+            list.iter().for_each(|item| count_into(item, counts));
+        }
+        Value::Map(map) => {
+            counts.map += 1;
+            map.values().for_each(|item| count_into(item, counts));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Binary, Timestamp};
+
+    #[test]
+    fn test_count_by_type() {
+        let value = Value::from([
+            ("name", Value::from("demo")),
+            ("enabled", Value::Bool(true)),
+            ("ratio", Value::Float(0.5)),
+            ("created", Value::Timestamp(Timestamp::UNIX_EPOCH)),
+            ("ttl", Value::Duration(core::time::Duration::from_secs(60))),
+            ("blob", Value::Binary(Binary(vec![1, 2]))),
+            (
+                "items",
+                Value::from([
+                    Value::Int(1),
+                    Value::Null,
+                    Value::from([("id", Value::Int(2)), ("tag", Value::from("x"))]),
+                    Value::List(vec![]),
+                ]),
+            ),
+        ]);
+
+        assert_eq!(
+            value.count_by_type(),
+            TypeCounts {
+                null: 1,
+                bool: 1,
+                int: 2,
+                float: 1,
+                string: 2,
+                binary: 1,
+                timestamp: 1,
+                duration: 1,
+                list: 2,
+                map: 2,
+            }
+        );
+        assert_eq!(value.count_by_type().total(), 14);
+        assert_eq!(Value::Int(1).count_by_type().total(), 1);
+    }
+}
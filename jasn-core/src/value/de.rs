@@ -2,6 +2,11 @@
 //!
 //! This module provides deserialization from JASN `Value` to Rust types.
 
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
 use serde::de::{
     self, Deserialize, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
 };
@@ -25,17 +30,36 @@ pub enum Error {
     /// Invalid value encountered.
     #[error("invalid value: {0}")]
     InvalidValue(String),
+    /// A number does not fit in the target type.
+    #[error("{value} is out of range for {target}")]
+    OutOfRange {
+        /// The number as written in the value.
+        value: String,
+        /// Rust type that was requested.
+        target: &'static str,
+    },
+    /// A required struct field is absent from the map.
+    #[error("missing field `{0}`")]
+    MissingField(&'static str),
 }
 
 impl de::Error for Error {
-    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+    fn custom<T: core::fmt::Display>(msg: T) -> Self {
         Error::Custom(msg.to_string())
     }
+
+    fn missing_field(field: &'static str) -> Self {
+        Error::MissingField(field)
+    }
 }
 
-type Result<T> = std::result::Result<T, Error>;
+type Result<T> = core::result::Result<T, Error>;
 
 /// Deserialize a JASN [`Value`] into a Rust value.
+///
+/// Strings, map keys and binary data are lent out with the `value` lifetime, so `&str`,
+/// `&[u8]` (via `serde_bytes`) and `#[serde(borrow)]` `Cow<str>` fields borrow from `value`
+/// instead of copying.
 pub fn from_value<'de, T>(value: &'de Value) -> Result<T>
 where
     T: Deserialize<'de>,
@@ -59,12 +83,13 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
             Value::Bool(v) => visitor.visit_bool(*v),
             Value::Int(v) => visitor.visit_i64(*v),
             Value::Float(v) => visitor.visit_f64(*v),
-            Value::String(v) => visitor.visit_str(v),
-            Value::Binary(v) => visitor.visit_bytes(&v.0),
-            Value::Timestamp(_) => Err(Error::InvalidValue(
-                "timestamps must be deserialized explicitly".to_string(),
-            )),
-            Value::List(v) => visitor.visit_seq(SeqDeserializer { iter: v.iter() }),
+            Value::String(v) => visitor.visit_borrowed_str(v),
+            Value::Binary(v) => visitor.visit_borrowed_bytes(&v.0),
+            // Self-describing formats have no timestamp type, so use the RFC 3339 string that
+            // deserialize_str also produces
+            Value::Timestamp(t) => visitor.visit_string(super::timestamp::to_rfc3339(t)),
+            Value::Duration(d) => visitor.visit_string(super::duration::to_iso8601(d)),
+            Value::List(v) => visit_list(v, visitor),
             Value::Map(v) => visitor.visit_map(MapDeserializer {
                 iter: v.iter(),
                 value: None,
@@ -137,6 +162,19 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
         }
     }
 
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Int(v) => visitor.visit_i128(i128::from(*v)),
+            other => Err(Error::TypeMismatch {
+                expected: "i128".to_string(),
+                got: type_name(other),
+            }),
+        }
+    }
+
     fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
@@ -189,11 +227,38 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
         }
     }
 
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Int(v) => match u128::try_from(*v) {
+                Ok(v) => visitor.visit_u128(v),
+                Err(_) => Err(Error::OutOfRange {
+                    value: v.to_string(),
+                    target: "u128",
+                }),
+            },
+            other => Err(Error::TypeMismatch {
+                expected: "u128".to_string(),
+                got: type_name(other),
+            }),
+        }
+    }
+
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
         match self.value {
+            // Finite values beyond f32::MAX would silently become infinite; infinities and NaN
+            // carry over as-is and in-range values are rounded to the nearest f32
+            Value::Float(v) if v.is_finite() && v.abs() > f32::MAX as f64 => {
+                Err(Error::OutOfRange {
+                    value: v.to_string(),
+                    target: "f32",
+                })
+            }
             Value::Float(v) => visitor.visit_f32(*v as f32),
             Value::Int(v) => visitor.visit_f32(*v as f32),
             other => Err(Error::TypeMismatch {
@@ -247,7 +312,10 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
         V: Visitor<'de>,
     {
         match self.value {
-            Value::String(v) => visitor.visit_str(v),
+            Value::String(v) => visitor.visit_borrowed_str(v),
+            // Lets `ts"..."` literals feed string-based adapters like `time::serde::rfc3339`
+            Value::Timestamp(t) => visitor.visit_string(super::timestamp::to_rfc3339(t)),
+            Value::Duration(d) => visitor.visit_string(super::duration::to_iso8601(d)),
             other => Err(Error::TypeMismatch {
                 expected: "string".to_string(),
                 got: type_name(other),
@@ -267,7 +335,7 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
         V: Visitor<'de>,
     {
         match self.value {
-            Value::Binary(v) => visitor.visit_bytes(&v.0),
+            Value::Binary(v) => visitor.visit_borrowed_bytes(&v.0),
             other => Err(Error::TypeMismatch {
                 expected: "bytes".to_string(),
                 got: type_name(other),
@@ -324,7 +392,9 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
         V: Visitor<'de>,
     {
         match self.value {
-            Value::List(v) => visitor.visit_seq(SeqDeserializer { iter: v.iter() }),
+            Value::List(v) => visit_list(v, visitor),
+            // Lets byte arrays such as `[u8; 16]` and `Vec<u8>` read binary data
+            Value::Binary(v) => visit_bytes_as_seq(&v.0, visitor),
             other => Err(Error::TypeMismatch {
                 expected: "array".to_string(),
                 got: type_name(other),
@@ -422,8 +492,37 @@ impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     }
 }
 
+/// Visits `list` as a sequence, failing if the visitor leaves elements unread (such as a
+/// three-element list deserialized into a pair).
+fn visit_list<'de, V>(list: &'de [Value], visitor: V) -> Result<V::Value>
+where
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer { iter: list.iter() };
+    let value = visitor.visit_seq(&mut seq)?;
+    if seq.iter.len() == 0 {
+        Ok(value)
+    } else {
+        Err(de::Error::invalid_length(
+            list.len(),
+            &"fewer elements in list",
+        ))
+    }
+}
+
+/// Visits `bytes` as a sequence of `u8`, failing if the visitor leaves any unread.
+fn visit_bytes_as_seq<'de, V>(bytes: &[u8], visitor: V) -> Result<V::Value>
+where
+    V: Visitor<'de>,
+{
+    let mut seq = de::value::SeqDeserializer::new(bytes.iter().copied());
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
 struct SeqDeserializer<'de> {
-    iter: std::slice::Iter<'de, Value>,
+    iter: core::slice::Iter<'de, Value>,
 }
 
 impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
@@ -441,7 +540,7 @@ impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
 }
 
 struct MapDeserializer<'de> {
-    iter: std::collections::btree_map::Iter<'de, String, Value>,
+    iter: alloc::collections::btree_map::Iter<'de, String, Value>,
     value: Option<&'de Value>,
 }
 
@@ -455,7 +554,7 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
         match self.iter.next() {
             Some((key, value)) => {
                 self.value = Some(value);
-                seed.deserialize(key.as_str().into_deserializer()).map(Some)
+                seed.deserialize(MapKeyDeserializer { key }).map(Some)
             }
             None => Ok(None),
         }
@@ -472,6 +571,82 @@ impl<'de> MapAccess<'de> for MapDeserializer<'de> {
     }
 }
 
+/// Deserializer for map keys, which are always strings in JASN.
+///
+/// Integer keys (written as strings by the serializer, e.g. for `HashMap<u32, _>`) are parsed
+/// back from their text; every other type sees the key as a string.
+struct MapKeyDeserializer<'de> {
+    key: &'de str,
+}
+
+macro_rules! deserialize_parsed_key {
+    ($($method:ident => $visit:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                match self.key.parse::<$ty>() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(_) => Err(Error::InvalidValue(format!(
+                        "map key {:?} is not a valid {}",
+                        self.key,
+                        stringify!($ty)
+                    ))),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for MapKeyDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_str(self.key)
+    }
+
+    deserialize_parsed_key! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self.key.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
 struct EnumDeserializer<'de> {
     key: &'de String,
     value: &'de Value,
@@ -549,6 +724,7 @@ fn type_name(value: &Value) -> String {
         Value::String(_) => "string",
         Value::Binary(_) => "binary",
         Value::Timestamp(_) => "timestamp",
+        Value::Duration(_) => "duration",
         Value::List(_) => "lists",
         Value::Map(_) => "map",
     }
@@ -0,0 +1,139 @@
+use core::fmt;
+
+use super::{Value, duration, timestamp};
+
+/// `{:?}` prints the enum structure (`Map({"a": Int(1)})`), so assertion failures show exact
+/// variants. `{:#?}` prints JASN-like pretty text instead:
+///
+/// ```
+/// use jasn_core::Value;
+///
+/// let value = Value::from([("a", Value::from([1, 2]))]);
+/// assert_eq!(format!("{value:?}"), r#"Map({"a": List([Int(1), Int(2)])})"#);
+/// assert_eq!(format!("{value:#?}"), "{\n  a: [\n    1,\n    2,\n  ],\n}");
+/// ```
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return write_jasn(self, f, 0);
+        }
+
+        match self {
+            Value::Null => f.write_str("Null"),
+            Value::Bool(b) => f.debug_tuple("Bool").field(b).finish(),
+            Value::Int(i) => f.debug_tuple("Int").field(i).finish(),
+            Value::Float(x) => f.debug_tuple("Float").field(x).finish(),
+            Value::String(s) => f.debug_tuple("String").field(s).finish(),
+            Value::Binary(b) => f.debug_tuple("Binary").field(b).finish(),
+            Value::Timestamp(t) => f.debug_tuple("Timestamp").field(t).finish(),
+            Value::Duration(d) => f.debug_tuple("Duration").field(d).finish(),
+            Value::List(list) => f.debug_tuple("List").field(list).finish(),
+            Value::Map(map) => f.debug_tuple("Map").field(map).finish(),
+        }
+    }
+}
+
+const INDENT: &str = "  ";
+
+fn write_jasn(value: &Value, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+    match value {
+        Value::Null => f.write_str("null"),
+        Value::Bool(b) => write!(f, "{b}"),
+        Value::Int(i) => write!(f, "{i}"),
+        Value::Float(x) if x.is_nan() => f.write_str("nan"),
+        Value::Float(x) if x.is_infinite() => {
+            f.write_str(if x.is_sign_negative() { "-inf" } else { "inf" })
+        }
+        // `{:?}` keeps the `.0` on whole floats
+        Value::Float(x) => write!(f, "{x:?}"),
+        Value::String(s) => write!(f, "{s:?}"),
+        Value::Binary(b) => write!(f, "b64\"{b}\""),
+        Value::Timestamp(t) => write!(f, "ts\"{}\"", timestamp::to_rfc3339(t)),
+        Value::Duration(d) => write!(f, "dur\"{}\"", duration::to_iso8601(d)),
+        Value::List(list) if list.is_empty() => f.write_str("[]"),
+        Value::List(list) => {
+            f.write_str("[\n")?;
+            for item in list {
+                write_indent(f, depth + 1)?;
+                write_jasn(item, f, depth + 1)?;
+                f.write_str(",\n")?;
+            }
+            write_indent(f, depth)?;
+            f.write_str("]")
+        }
+        Value::Map(map) if map.is_empty() => f.write_str("{}"),
+        Value::Map(map) => {
+            f.write_str("{\n")?;
+            for (key, item) in map {
+                write_indent(f, depth + 1)?;
+                if is_identifier(key) {
+                    write!(f, "{key}: ")?;
+                } else {
+                    write!(f, "{key:?}: ")?;
+                }
+                write_jasn(item, f, depth + 1)?;
+                f.write_str(",\n")?;
+            }
+            write_indent(f, depth)?;
+            f.write_str("}")
+        }
+    }
+}
+
+fn write_indent(f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+    (0..depth).try_for_each(|_| f.write_str(INDENT))
+}
+
+/// Whether `key` can be written without quotes.
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+    use crate::{Binary, Timestamp};
+
+    #[test]
+    fn test_alternate_debug_reads_like_jasn() {
+        let value = Value::from([
+            ("name", Value::from("say \"hi\"")),
+            ("ratio", Value::Float(2.0)),
+            (
+                "limits",
+                Value::from([Value::Float(f64::NEG_INFINITY), Value::Null]),
+            ),
+            ("data", Value::Binary(Binary(b"Hello".to_vec()))),
+            ("created at", Value::Timestamp(Timestamp::UNIX_EPOCH)),
+            ("ttl", Value::Duration(core::time::Duration::from_secs(90))),
+            ("empty", Value::List(vec![])),
+        ]);
+
+        let expected = r#"{
+  "created at": ts"1970-01-01T00:00:00Z",
+  data: b64"SGVsbG8=",
+  empty: [],
+  limits: [
+    -inf,
+    null,
+  ],
+  name: "say \"hi\"",
+  ratio: 2.0,
+  ttl: dur"PT1M30S",
+}"#;
+        assert_eq!(format!("{value:#?}"), expected);
+    }
+
+    #[test]
+    fn test_debug_is_terse() {
+        let value = Value::from([("a", Value::from([Value::Int(1), Value::Null]))]);
+        assert_eq!(format!("{value:?}"), r#"Map({"a": List([Int(1), Null])})"#);
+        assert_eq!(value.to_string(), format!("{value:?}"));
+    }
+}
@@ -0,0 +1,248 @@
+use alloc::string::String;
+use core::{fmt::Write, time::Duration};
+
+const SECS_PER_MINUTE: u64 = 60;
+const SECS_PER_HOUR: u64 = 60 * SECS_PER_MINUTE;
+const SECS_PER_DAY: u64 = 24 * SECS_PER_HOUR;
+const SECS_PER_WEEK: u64 = 7 * SECS_PER_DAY;
+
+/// Error returned by [`from_iso8601`] for text that is not a supported ISO 8601 duration.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum DurationError {
+    /// The text does not follow the `PnWnDTnHnMnS` layout.
+    #[error("expected an ISO 8601 duration such as PT1H30M")]
+    Syntax,
+    /// Years (`Y`) and months (`M` before `T`) are not a fixed number of seconds.
+    #[error("years and months have no fixed length; use weeks, days or smaller units")]
+    CalendarUnit,
+    /// The total does not fit in a [`Duration`].
+    #[error("duration is too long")]
+    Overflow,
+}
+
+/// Parses an ISO 8601 duration such as `PT1H30M`, `P1DT2H` or `PT0.25S`.
+///
+/// Weeks, days, hours, minutes and seconds are supported, with a day counted as 24 hours. Only
+/// the seconds component may have a fraction (up to nanosecond precision); years and months
+/// are rejected because their length depends on the calendar.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use jasn_core::duration;
+///
+/// assert_eq!(duration::from_iso8601("PT1H30M").unwrap(), Duration::from_secs(5400));
+/// assert!(duration::from_iso8601("P1Y").is_err());
+/// ```
+pub fn from_iso8601(text: &str) -> Result<Duration, DurationError> {
+    let rest = text.strip_prefix('P').ok_or(DurationError::Syntax)?;
+    let (date, time) = match rest.split_once('T') {
+        Some((_, "")) => return Err(DurationError::Syntax),
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+
+    let mut secs = 0u64;
+    let mut nanos = 0u32;
+    let mut components = 0;
+    let mut add = |count: u64, unit: u64| -> Result<(), DurationError> {
+        components += 1;
+        secs = count
+            .checked_mul(unit)
+            .and_then(|s| secs.checked_add(s))
+            .ok_or(DurationError::Overflow)?;
+        Ok(())
+    };
+
+    let mut order = 0;
+    for_each_component(date, |count, fraction, designator| {
+        let (rank, unit) = match designator {
+            'Y' | 'M' => return Err(DurationError::CalendarUnit),
+            'W' => (1, SECS_PER_WEEK),
+            'D' => (2, SECS_PER_DAY),
+            _ => return Err(DurationError::Syntax),
+        };
+        if rank <= order || fraction.is_some() {
+            return Err(DurationError::Syntax);
+        }
+        order = rank;
+        add(count, unit)
+    })?;
+
+    if let Some(time) = time {
+        let mut order = 0;
+        for_each_component(time, |count, fraction, designator| {
+            let (rank, unit) = match designator {
+                'H' => (1, SECS_PER_HOUR),
+                'M' => (2, SECS_PER_MINUTE),
+                'S' => (3, 1),
+                _ => return Err(DurationError::Syntax),
+            };
+            if rank <= order || (fraction.is_some() && designator != 'S') {
+                return Err(DurationError::Syntax);
+            }
+            order = rank;
+            if let Some(fraction) = fraction {
+                nanos = parse_nanos(fraction)?;
+            }
+            add(count, unit)
+        })?;
+    }
+
+    if components == 0 {
+        return Err(DurationError::Syntax);
+    }
+    Ok(Duration::new(secs, nanos))
+}
+
+/// Formats a duration in ISO 8601 form, such as `P1DT2H` or `PT1.5S`.
+///
+/// Whole days are written as `D` and the rest as hours, minutes and seconds, omitting zero
+/// components; the zero duration is `PT0S`. The output always parses back with
+/// [`from_iso8601`] to the same duration.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use jasn_core::duration;
+///
+/// assert_eq!(duration::to_iso8601(&Duration::from_secs(93_600)), "P1DT2H");
+/// assert_eq!(duration::to_iso8601(&Duration::from_millis(1500)), "PT1.5S");
+/// ```
+pub fn to_iso8601(duration: &Duration) -> String {
+    let total = duration.as_secs();
+    let (days, rest) = (total / SECS_PER_DAY, total % SECS_PER_DAY);
+    let (hours, rest) = (rest / SECS_PER_HOUR, rest % SECS_PER_HOUR);
+    let (minutes, seconds) = (rest / SECS_PER_MINUTE, rest % SECS_PER_MINUTE);
+    let nanos = duration.subsec_nanos();
+
+    let mut out = String::from("P");
+    if days > 0 {
+        let _ = write!(out, "{days}D");
+    }
+    if rest > 0 || hours > 0 || nanos > 0 || days == 0 {
+        out.push('T');
+        if hours > 0 {
+            let _ = write!(out, "{hours}H");
+        }
+        if minutes > 0 {
+            let _ = write!(out, "{minutes}M");
+        }
+        if seconds > 0 || nanos > 0 || (hours == 0 && minutes == 0) {
+            let _ = write!(out, "{seconds}");
+            if nanos > 0 {
+                let digits = alloc::format!("{nanos:09}");
+                out.push('.');
+                out.push_str(digits.trim_end_matches('0'));
+            }
+            out.push('S');
+        }
+    }
+    out
+}
+
+/// Calls `f` with the number, optional fraction digits and designator of each `<n><unit>`
+/// component in `text`.
+fn for_each_component(
+    mut text: &str,
+    mut f: impl FnMut(u64, Option<&str>, char) -> Result<(), DurationError>,
+) -> Result<(), DurationError> {
+    while !text.is_empty() {
+        let end = text
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or(DurationError::Syntax)?;
+        let (digits, rest) = text.split_at(end);
+        if digits.is_empty() {
+            return Err(DurationError::Syntax);
+        }
+        let count = digits.parse().map_err(|_| DurationError::Overflow)?;
+
+        let (fraction, rest) = match rest.strip_prefix(['.', ',']) {
+            Some(rest) => {
+                let end = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .ok_or(DurationError::Syntax)?;
+                (Some(&rest[..end]), &rest[end..])
+            }
+            None => (None, rest),
+        };
+
+        let mut chars = rest.chars();
+        let designator = chars.next().ok_or(DurationError::Syntax)?;
+        f(count, fraction, designator)?;
+        text = chars.as_str();
+    }
+    Ok(())
+}
+
+fn parse_nanos(fraction: &str) -> Result<u32, DurationError> {
+    if fraction.is_empty() || fraction.len() > 9 {
+        return Err(DurationError::Syntax);
+    }
+    let digits: u32 = fraction.parse().map_err(|_| DurationError::Syntax)?;
+    Ok(digits * 10u32.pow(9 - fraction.len() as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("PT1H30M", Duration::from_secs(5400))]
+    #[case("P1DT2H", Duration::from_secs(93_600))]
+    #[case("P2W", Duration::from_secs(14 * SECS_PER_DAY))]
+    #[case("P1W2D", Duration::from_secs(9 * SECS_PER_DAY))]
+    #[case("PT0S", Duration::ZERO)]
+    #[case("P0D", Duration::ZERO)]
+    #[case("PT90S", Duration::from_secs(90))]
+    #[case("PT1.5S", Duration::from_millis(1500))]
+    #[case("PT0,25S", Duration::from_millis(250))]
+    #[case("PT0.000000001S", Duration::from_nanos(1))]
+    #[case("P1DT1H1M1.1S", Duration::new(SECS_PER_DAY + 3661, 100_000_000))]
+    fn test_from_iso8601(#[case] text: &str, #[case] expected: Duration) {
+        assert_eq!(from_iso8601(text), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("", DurationError::Syntax)]
+    #[case("P", DurationError::Syntax)]
+    #[case("PT", DurationError::Syntax)]
+    #[case("1H", DurationError::Syntax)]
+    #[case("pt1h", DurationError::Syntax)]
+    #[case("PT1", DurationError::Syntax)]
+    #[case("PTH", DurationError::Syntax)]
+    #[case("P1H", DurationError::Syntax)]
+    #[case("PT1D", DurationError::Syntax)]
+    #[case("PT1M1H", DurationError::Syntax)]
+    #[case("PT1H1H", DurationError::Syntax)]
+    #[case("P1D1W", DurationError::Syntax)]
+    #[case("PT1.5H", DurationError::Syntax)]
+    #[case("PT1.S", DurationError::Syntax)]
+    #[case("PT1.0000000001S", DurationError::Syntax)]
+    #[case("PT-1S", DurationError::Syntax)]
+    #[case("P1Y", DurationError::CalendarUnit)]
+    #[case("P1M", DurationError::CalendarUnit)]
+    #[case("PT99999999999999999999S", DurationError::Overflow)]
+    #[case("P99999999999999W", DurationError::Overflow)]
+    fn test_from_iso8601_invalid(#[case] text: &str, #[case] expected: DurationError) {
+        assert_eq!(from_iso8601(text), Err(expected));
+    }
+
+    #[rstest]
+    #[case(Duration::ZERO, "PT0S")]
+    #[case(Duration::from_secs(5400), "PT1H30M")]
+    #[case(Duration::from_secs(93_600), "P1DT2H")]
+    #[case(Duration::from_secs(SECS_PER_DAY), "P1D")]
+    #[case(Duration::from_secs(SECS_PER_DAY + 1), "P1DT1S")]
+    #[case(Duration::from_millis(1500), "PT1.5S")]
+    #[case(Duration::from_nanos(1), "PT0.000000001S")]
+    #[case(Duration::from_secs(3600 + 5), "PT1H5S")]
+    #[case(Duration::MAX, "P213503982334601DT7H15.999999999S")]
+    fn test_to_iso8601(#[case] duration: Duration, #[case] expected: &str) {
+        assert_eq!(to_iso8601(&duration), expected);
+        assert_eq!(from_iso8601(expected), Ok(duration));
+    }
+}
@@ -0,0 +1,235 @@
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::Value;
+
+impl Value {
+    /// Flattens nested maps and lists into a single map keyed by paths.
+    ///
+    /// Map keys are joined with `separator` and list items are addressed as `[i]`, so
+    /// `{a: {b: [1, 2]}}` becomes `{"a.b[0]": 1, "a.b[1]": 2}` with `"."` as separator.
+    /// Scalars, empty maps and empty lists are kept as leaf values; a scalar at the root is
+    /// stored under the empty key.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let value = Value::from([("a", Value::from([("b", 1)]))]);
+    /// let flat = value.flatten(".");
+    /// assert_eq!(flat["a.b"], 1);
+    /// assert_eq!(Value::unflatten(flat, "."), value);
+    /// ```
+    pub fn flatten(&self, separator: &str) -> BTreeMap<String, Value> {
+        let mut flat = BTreeMap::new();
+        flatten_into(self, String::new(), separator, &mut flat);
+        flat
+    }
+
+    /// Rebuilds a nested value from a map produced by [`Self::flatten`].
+    ///
+    /// Missing list indices are filled with [`Value::Null`]. An index that is not smaller than
+    /// the number of entries in `flat` is kept as part of a literal map key instead, so
+    /// untrusted keys such as `"a[99999999999]"` cannot allocate huge lists. Round-trips are
+    /// exact unless the original map keys contain `separator`, `[`, or are empty. When paths
+    /// conflict (such as `"a"` and `"a.b"`), the path that sorts last wins.
+    pub fn unflatten(flat: BTreeMap<String, Value>, separator: &str) -> Value {
+        let max_len = flat.len();
+        let mut root = Value::Map(BTreeMap::new());
+        for (path, value) in flat {
+            insert_path(&mut root, &parse_path(&path, separator, max_len), value);
+        }
+        root
+    }
+}
+
+/// A single step of a flattened path.
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+fn flatten_into(value: &Value, path: String, separator: &str, flat: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Map(map) if !map.is_empty() => {
+            for (key, item) in map {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}{separator}{key}")
+                };
+                flatten_into(item, child, separator, flat);
+            }
+        }
+        Value::List(list) if !list.is_empty() => {
+            for (i, item) in list.iter().enumerate() {
+                flatten_into(item, format!("{path}[{i}]"), separator, flat);
+            }
+        }
+        _ => {
+            flat.insert(path, value.clone());
+        }
+    }
+}
+
+/// Splits a flattened path into steps, accepting only list indices below `max_len`.
+fn parse_path(path: &str, separator: &str, max_len: usize) -> Vec<Step> {
+    let mut steps = Vec::new();
+    if path.is_empty() {
+        return steps;
+    }
+
+    for (n, mut part) in path.split(separator).enumerate() {
+        // Peel trailing `[i]` indices off the segment
+        let mut indices = Vec::new();
+        while let Some(rest) = part.strip_suffix(']')
+            && let Some(open) = rest.rfind('[')
+            && let Ok(index) = rest[open + 1..].parse::<usize>()
+            && index < max_len
+        {
+            indices.push(index);
+            part = &rest[..open];
+        }
+
+        // A leading `[i]` on the first segment indexes into a root list
+        if n > 0 || !part.is_empty() || indices.is_empty() {
+            steps.push(Step::Key(part.to_string()));
+        }
+        steps.extend(indices.into_iter().rev().map(Step::Index));
+    }
+
+    steps
+}
+
+fn insert_path(target: &mut Value, steps: &[Step], value: Value) {
+    let Some((step, rest)) = steps.split_first() else {
+        *target = value;
+        return;
+    };
+
+    match step {
+        Step::Key(key) => {
+            if !target.is_map() {
+                *target = Value::Map(BTreeMap::new());
+            }
+            if let Value::Map(map) = target {
+                insert_path(map.entry(key.clone()).or_default(), rest, value);
+            }
+        }
+        Step::Index(index) => {
+            if !target.is_list() {
+                *target = Value::List(Vec::new());
+            }
+            if let Value::List(list) = target {
+                if list.len() <= *index {
+                    list.resize(index + 1, Value::Null);
+                }
+                insert_path(&mut list[*index], rest, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn nested() -> Value {
+        Value::from([
+            (
+                "server",
+                Value::from([
+                    ("host", Value::from("localhost")),
+                    ("ports", Value::from([80, 443])),
+                ]),
+            ),
+            (
+                "users",
+                Value::from([
+                    Value::from([("name", "alice")]),
+                    Value::from([("name", "bob")]),
+                ]),
+            ),
+            ("empty", Value::Map(BTreeMap::new())),
+            ("debug", Value::Bool(true)),
+        ])
+    }
+
+    #[test]
+    fn test_flatten() {
+        let flat = nested().flatten(".");
+
+        let expected: BTreeMap<String, Value> = [
+            ("debug", Value::Bool(true)),
+            ("empty", Value::Map(BTreeMap::new())),
+            ("server.host", Value::from("localhost")),
+            ("server.ports[0]", Value::Int(80)),
+            ("server.ports[1]", Value::Int(443)),
+            ("users[0].name", Value::from("alice")),
+            ("users[1].name", Value::from("bob")),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        assert_eq!(flat, expected);
+    }
+
+    #[rstest]
+    #[case(nested(), ".")]
+    #[case(nested(), "__")]
+    #[case(Value::from([1, 2, 3]), ".")]
+    #[case(Value::from([Value::from([1]), Value::List(vec![])]), ".")]
+    #[case(Value::Int(42), ".")]
+    #[case(Value::Map(BTreeMap::new()), ".")]
+    fn test_flatten_round_trip(#[case] value: Value, #[case] separator: &str) {
+        assert_eq!(Value::unflatten(value.flatten(separator), separator), value);
+    }
+
+    #[test]
+    fn test_unflatten_fills_missing_indices() {
+        let flat = BTreeMap::from([
+            ("a[0]".to_string(), Value::Int(0)),
+            ("a[2]".to_string(), Value::Int(2)),
+            ("b".to_string(), Value::Bool(true)),
+        ]);
+        assert_eq!(
+            Value::unflatten(flat, "."),
+            Value::from([
+                (
+                    "a",
+                    Value::from([Value::Int(0), Value::Null, Value::Int(2)])
+                ),
+                ("b", Value::Bool(true)),
+            ])
+        );
+    }
+
+    #[rstest]
+    #[case("a[18446744073709551615]")]
+    #[case("a[99999999999]")]
+    #[case("a[1]")]
+    fn test_unflatten_out_of_range_index_is_key(#[case] path: &str) {
+        let flat = BTreeMap::from([(path.to_string(), Value::Int(1))]);
+        assert_eq!(Value::unflatten(flat, "."), Value::from([(path, 1)]));
+    }
+
+    #[test]
+    fn test_unflatten_out_of_range_nested_index() {
+        let flat = BTreeMap::from([
+            ("a[0][99999999999]".to_string(), Value::Int(1)),
+            ("b[1].c".to_string(), Value::Int(2)),
+        ]);
+        assert_eq!(
+            Value::unflatten(flat, "."),
+            Value::from([
+                ("a[0][99999999999]", Value::Int(1)),
+                ("b", Value::from([Value::Null, Value::from([("c", 2)])])),
+            ])
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use alloc::string::{String, ToString};
+
+use super::Value;
+
+/// Error returned by the pointer-based editing methods on [`Value`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum PatchError {
+    /// The pointer is neither empty nor starts with `/`.
+    #[error("invalid JSON pointer: {0:?}")]
+    InvalidPointer(String),
+    /// The pointer does not resolve to an existing value.
+    #[error("no value at {0:?}")]
+    NotFound(String),
+}
+
+impl Value {
+    /// Replaces the value at a JSON Pointer and returns the previous value.
+    ///
+    /// Like the RFC 6902 `replace` operation, the target must already exist; the empty pointer
+    /// replaces the whole value. Pointers resolve as in [`Self::pointer`], including negative
+    /// list positions.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let mut value = Value::from([("server", Value::from([("port", 80)]))]);
+    /// let old = value.replace_at("/server/port", Value::Int(8080)).unwrap();
+    /// assert_eq!(old, 80);
+    /// assert_eq!(value.pointer("/server/port"), Some(&Value::Int(8080)));
+    /// assert!(value.replace_at("/server/host", Value::Null).is_err());
+    /// ```
+    pub fn replace_at(&mut self, pointer: &str, value: Value) -> Result<Value, PatchError> {
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(PatchError::InvalidPointer(pointer.to_string()));
+        }
+        let target = self
+            .pointer_mut(pointer)
+            .ok_or_else(|| PatchError::NotFound(pointer.to_string()))?;
+        Ok(core::mem::replace(target, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn sample() -> Value {
+        Value::from([
+            ("name", Value::from("app")),
+            (
+                "servers",
+                Value::from([Value::from([("port", 80)]), Value::from([("port", 443)])]),
+            ),
+        ])
+    }
+
+    #[rstest]
+    #[case("/name", Value::from("app"))]
+    #[case("/servers/1/port", Value::Int(443))]
+    #[case("/servers/-1/port", Value::Int(443))]
+    #[case("/servers/0", Value::from([("port", 80)]))]
+    #[case("", sample())]
+    fn test_replace_at(#[case] pointer: &str, #[case] old: Value) {
+        let mut value = sample();
+        assert_eq!(value.replace_at(pointer, Value::Bool(true)), Ok(old));
+        assert_eq!(value.pointer(pointer), Some(&Value::Bool(true)));
+    }
+
+    #[rstest]
+    #[case("/missing", PatchError::NotFound("/missing".to_string()))]
+    #[case("/servers/2", PatchError::NotFound("/servers/2".to_string()))]
+    #[case("/servers/-", PatchError::NotFound("/servers/-".to_string()))]
+    #[case("/name/x", PatchError::NotFound("/name/x".to_string()))]
+    #[case("name", PatchError::InvalidPointer("name".to_string()))]
+    fn test_replace_at_errors(#[case] pointer: &str, #[case] error: PatchError) {
+        let mut value = sample();
+        assert_eq!(value.replace_at(pointer, Value::Null), Err(error));
+        assert_eq!(value, sample());
+    }
+}
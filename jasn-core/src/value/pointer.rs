@@ -0,0 +1,155 @@
+use alloc::borrow::Cow;
+
+use super::Value;
+
+impl Value {
+    /// Looks up a value by [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON Pointer.
+    ///
+    /// The empty pointer refers to the whole value; otherwise each `/`-separated token selects a
+    /// map key or list position, with `~1` and `~0` standing for `/` and `~` inside keys.
+    ///
+    /// As a non-standard convenience, a negative position such as `-1` counts from the end of a
+    /// list. The RFC's `-` token (one past the last element) never resolves here, since it only
+    /// names an insertion point.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let value = Value::from([("items", [10, 20, 30])]);
+    /// assert_eq!(value.pointer("/items/0"), Some(&Value::Int(10)));
+    /// assert_eq!(value.pointer("/items/-1"), Some(&Value::Int(30)));
+    /// assert_eq!(value.pointer("/items/-"), None);
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        tokens(pointer)?.try_fold(self, |target, token| match target {
+            Value::Map(map) => map.get(token.as_ref()),
+            Value::List(list) => list.get(list_index(&token, list.len())?),
+            _ => None,
+        })
+    }
+
+    /// Looks up a value by JSON Pointer and returns a mutable reference; see [`Self::pointer`].
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        tokens(pointer)?.try_fold(self, |target, token| match target {
+            Value::Map(map) => map.get_mut(token.as_ref()),
+            Value::List(list) => {
+                let index = list_index(&token, list.len())?;
+                list.get_mut(index)
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Splits a pointer into unescaped reference tokens, or `None` if it is not a valid pointer.
+fn tokens(pointer: &str) -> Option<impl Iterator<Item = Cow<'_, str>>> {
+    let rest = if pointer.is_empty() {
+        None
+    } else {
+        Some(pointer.strip_prefix('/')?)
+    };
+    Some(
+        rest.into_iter()
+            .flat_map(|rest| rest.split('/'))
+            .map(unescape),
+    )
+}
+
+fn unescape(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Resolves a list position token against a list of `len` elements.
+///
+/// Accepts RFC 6901 indices (digits without leading zeros) and negative indices counting from
+/// the end. Returns `None` for `-` and anything else that does not name an element.
+fn list_index(token: &str, len: usize) -> Option<usize> {
+    let (negative, digits) = match token.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, token),
+    };
+    let valid = !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'));
+    if !valid {
+        return None;
+    }
+
+    let index: usize = digits.parse().ok()?;
+    match negative {
+        false => Some(index),
+        true if index == 0 => None,
+        true => len.checked_sub(index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn sample() -> Value {
+        Value::from([
+            ("list", Value::from([10, 20, 30])),
+            ("a/b", Value::Int(1)),
+            ("m~n", Value::Int(2)),
+            ("", Value::Int(3)),
+            (
+                "nested",
+                Value::from([("inner", Value::from([[1, 2], [3, 4]]))]),
+            ),
+        ])
+    }
+
+    #[rstest]
+    #[case("", Some(sample()))]
+    #[case("/list/0", Some(Value::Int(10)))]
+    #[case("/list/2", Some(Value::Int(30)))]
+    #[case("/list/-1", Some(Value::Int(30)))]
+    #[case("/list/-3", Some(Value::Int(10)))]
+    #[case("/nested/inner/-1/0", Some(Value::Int(3)))]
+    #[case("/a~1b", Some(Value::Int(1)))]
+    #[case("/m~0n", Some(Value::Int(2)))]
+    #[case("/", Some(Value::Int(3)))]
+    #[case("/list/3", None)]
+    #[case("/list/-4", None)]
+    #[case("/list/-0", None)]
+    #[case("/list/-", None)]
+    #[case("/list/01", None)]
+    #[case("/list/+1", None)]
+    #[case("/list/x", None)]
+    #[case("/list/0/0", None)]
+    #[case("/missing", None)]
+    #[case("list", None)]
+    fn test_pointer(#[case] pointer: &str, #[case] expected: Option<Value>) {
+        assert_eq!(sample().pointer(pointer), expected.as_ref());
+    }
+
+    #[test]
+    fn test_pointer_mut() {
+        let mut value = sample();
+        *value.pointer_mut("/list/-1").unwrap() = Value::Int(99);
+        *value.pointer_mut("/nested/inner/0/1").unwrap() = Value::Null;
+        assert!(value.pointer_mut("/list/-").is_none());
+
+        assert_eq!(value.pointer("/list"), Some(&Value::from([10, 20, 99])));
+        assert_eq!(value.pointer("/nested/inner/0/1"), Some(&Value::Null));
+    }
+
+    #[rstest]
+    #[case("7", 3, Some(7))]
+    #[case("-1", 3, Some(2))]
+    #[case("-3", 3, Some(0))]
+    #[case("-4", 3, None)]
+    #[case("-", 3, None)]
+    #[case("", 3, None)]
+    #[case("99999999999999999999999", 3, None)]
+    fn test_list_index(#[case] token: &str, #[case] len: usize, #[case] expected: Option<usize>) {
+        assert_eq!(list_index(token, len), expected);
+    }
+}
@@ -0,0 +1,101 @@
+use alloc::string::ToString;
+
+use super::Value;
+
+impl Value {
+    /// Returns a parallel tree in which every scalar is replaced by the name of its type.
+    ///
+    /// Maps and lists keep their shape, so the result reads as a structural outline of a sample
+    /// document. Type names are `null`, `bool`, `int`, `float`, `string`, `binary`,
+    /// `timestamp` and `duration`.
+    ///
+    /// ```
+    /// use jasn_core::Value;
+    ///
+    /// let value = Value::from([("id", Value::Int(7)), ("tags", Value::from(["a"]))]);
+    /// assert_eq!(
+    ///     value.type_tree(),
+    ///     Value::from([("id", Value::from("int")), ("tags", Value::from(["string"]))])
+    /// );
+    /// ```
+    pub fn type_tree(&self) -> Value {
+        match self {
+            Value::List(list) => Value::List(list.iter().map(Value::type_tree).collect()),
+            Value::Map(map) => Value::Map(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), value.type_tree()))
+                    .collect(),
+            ),
+            scalar => Value::String(type_name(scalar).to_string()),
+        }
+    }
+}
+
+pub(super) fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Int(_) => "int",
+        Value::Float(_) => "float",
+        Value::String(_) => "string",
+        Value::Binary(_) => "binary",
+        Value::Timestamp(_) => "timestamp",
+        Value::Duration(_) => "duration",
+        Value::List(_) => "list",
+        Value::Map(_) => "map",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{Binary, Timestamp};
+
+    #[test]
+    fn test_type_tree() {
+        let value = Value::from([
+            ("name", Value::from("demo")),
+            ("version", Value::Int(2)),
+            ("ratio", Value::Float(0.5)),
+            ("enabled", Value::Bool(true)),
+            ("icon", Value::Binary(Binary(vec![0xff]))),
+            ("created", Value::Timestamp(Timestamp::UNIX_EPOCH)),
+            ("parent", Value::Null),
+            (
+                "items",
+                Value::from([
+                    Value::from([("id", Value::Int(1)), ("tags", Value::from(["x"]))]),
+                    Value::Int(3),
+                ]),
+            ),
+            ("extra", Value::Map(BTreeMap::new())),
+        ]);
+
+        assert_eq!(
+            value.type_tree(),
+            Value::from([
+                ("name", Value::from("string")),
+                ("version", Value::from("int")),
+                ("ratio", Value::from("float")),
+                ("enabled", Value::from("bool")),
+                ("icon", Value::from("binary")),
+                ("created", Value::from("timestamp")),
+                ("parent", Value::from("null")),
+                (
+                    "items",
+                    Value::from([
+                        Value::from([
+                            ("id", Value::from("int")),
+                            ("tags", Value::from(["string"]))
+                        ]),
+                        Value::from("int"),
+                    ]),
+                ),
+                ("extra", Value::Map(BTreeMap::new())),
+            ])
+        );
+        assert_eq!(Value::Int(1).type_tree(), Value::from("int"));
+    }
+}
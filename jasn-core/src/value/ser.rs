@@ -2,7 +2,11 @@
 //!
 //! This module provides serialization from Rust types to JASN `Value`.
 
-use std::collections::BTreeMap;
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use serde::{Serialize, ser};
 
@@ -20,12 +24,12 @@ pub enum Error {
 }
 
 impl ser::Error for Error {
-    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+    fn custom<T: core::fmt::Display>(msg: T) -> Self {
         Error::Custom(msg.to_string())
     }
 }
 
-type Result<T> = std::result::Result<T, Error>;
+type Result<T> = core::result::Result<T, Error>;
 
 /// Serialize a Rust value to a JASN [`Value`].
 pub fn to_value<T>(value: &T) -> Result<Value>
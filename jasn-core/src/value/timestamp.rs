@@ -1,4 +1,128 @@
-use time::OffsetDateTime;
+use alloc::string::{String, ToString};
+
+use time::{
+    OffsetDateTime,
+    error::{ComponentRange, Parse},
+    format_description::well_known::Rfc3339,
+};
 
 /// Type alias for timestamps (RFC3339/ISO8601 compatible).
 pub type Timestamp = OffsetDateTime;
+
+/// Creates a UTC timestamp from seconds since the Unix epoch.
+///
+/// Fails if the result is outside the range `time` supports (years -9999 to 9999).
+///
+/// ```
+/// use jasn_core::timestamp;
+///
+/// let t = timestamp::from_unix(1_700_000_000).unwrap();
+/// assert_eq!(timestamp::to_rfc3339(&t), "2023-11-14T22:13:20Z");
+/// ```
+pub fn from_unix(secs: i64) -> Result<Timestamp, ComponentRange> {
+    OffsetDateTime::from_unix_timestamp(secs)
+}
+
+/// Parses an RFC 3339 timestamp such as `2024-01-15T12:30:45.5+01:00`, keeping its offset.
+pub fn from_rfc3339(text: &str) -> Result<Timestamp, Parse> {
+    OffsetDateTime::parse(text, &Rfc3339)
+}
+
+/// Returns the current time in UTC.
+#[cfg(feature = "std")]
+pub fn now() -> Timestamp {
+    OffsetDateTime::now_utc()
+}
+
+/// Formats a timestamp as RFC 3339, falling back to its [`Display`](core::fmt::Display) form
+/// when it can't be represented (years outside `0..=9999` or offsets with seconds).
+#[cfg(feature = "std")]
+pub fn to_rfc3339(t: &Timestamp) -> String {
+    t.format(&Rfc3339).unwrap_or_else(|_| t.to_string())
+}
+
+/// Formats a timestamp as RFC 3339, falling back to its [`Display`](core::fmt::Display) form
+/// when it can't be represented (years outside `0..=9999` or offsets with seconds).
+///
+/// `time` only formats with `std`, so this mirrors its `Rfc3339` output by hand.
+#[cfg(not(feature = "std"))]
+pub fn to_rfc3339(t: &Timestamp) -> String {
+    use core::fmt::Write;
+
+    let offset = t.offset();
+    if !(0..=9999).contains(&t.year()) || offset.seconds_past_minute() != 0 {
+        return t.to_string();
+    }
+
+    let mut out = String::with_capacity(35);
+    let _ = write!(
+        out,
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        t.year(),
+        u8::from(t.month()),
+        t.day(),
+        t.hour(),
+        t.minute(),
+        t.second()
+    );
+    if t.nanosecond() != 0 {
+        let nanos = alloc::format!("{:09}", t.nanosecond());
+        out.push('.');
+        out.push_str(nanos.trim_end_matches('0'));
+    }
+    if offset.is_utc() {
+        out.push('Z');
+    } else {
+        let sign = if offset.is_negative() { '-' } else { '+' };
+        let _ = write!(
+            out,
+            "{sign}{:02}:{:02}",
+            offset.whole_hours().unsigned_abs(),
+            offset.minutes_past_hour().unsigned_abs()
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_from_unix() {
+        let t = from_unix(0).unwrap();
+        assert_eq!(t, OffsetDateTime::UNIX_EPOCH);
+        assert_eq!(
+            from_unix(1_234_567_890).unwrap().unix_timestamp(),
+            1_234_567_890
+        );
+        assert!(from_unix(i64::MAX).is_err());
+    }
+
+    #[rstest]
+    #[case("2024-01-15T12:30:45Z", "2024-01-15T12:30:45Z")]
+    #[case("2024-01-15T12:30:45.500Z", "2024-01-15T12:30:45.5Z")]
+    #[case("2024-01-15T12:30:45-05:00", "2024-01-15T12:30:45-05:00")]
+    fn test_from_rfc3339(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(to_rfc3339(&from_rfc3339(text).unwrap()), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("2024-01-15")]
+    #[case("2024-13-01T00:00:00Z")]
+    fn test_from_rfc3339_invalid(#[case] text: &str) {
+        assert!(from_rfc3339(text).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_now() {
+        let before = OffsetDateTime::now_utc();
+        let t = now();
+        assert!(t >= before);
+        assert!(t.offset().is_utc());
+    }
+}
@@ -5,12 +5,17 @@ use std::{
     process,
 };
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
 use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use jasn::{
-    formatter::{BinaryEncoding, Options, QuoteStyle, TimestampPrecision, format_with_opts},
+    Value,
+    formatter::{
+        BinaryEncoding, Indent, Options, QuoteStyle, TimestampPrecision, header_comment,
+        try_format_with_opts,
+    },
     parse,
 };
+use similar::TextDiff;
 
 /// JASN - Just Another Serialization Notation CLI tool
 #[derive(Parser)]
@@ -34,21 +39,33 @@ enum Commands {
         #[arg(short, long, value_name = "FILE")]
         output: Option<PathBuf>,
 
+        /// Load formatting options from a JASN file (explicit flags take precedence)
+        #[arg(long, value_name = "FILE")]
+        config: Option<PathBuf>,
+
         /// Use compact format (no whitespace)
         #[arg(short, long)]
         compact: bool,
 
         /// Indentation string (default: 2 spaces)
-        #[arg(long, default_value = "  ")]
-        indent: String,
+        #[arg(long)]
+        indent: Option<String>,
+
+        /// Number of indent characters per level (alternative to --indent)
+        #[arg(long, value_name = "N", conflicts_with = "indent")]
+        indent_count: Option<usize>,
+
+        /// Indent character used with --indent-count
+        #[arg(long, value_enum, default_value = "space", requires = "indent_count")]
+        indent_char: IndentCharArg,
 
-        /// Quote style for strings
-        #[arg(long, value_enum, default_value = "double")]
-        quotes: QuoteStyleArg,
+        /// Quote style for strings (default: double)
+        #[arg(long, value_enum)]
+        quotes: Option<QuoteStyleArg>,
 
-        /// Binary encoding format
-        #[arg(long, value_enum, default_value = "base64")]
-        binary: BinaryEncodingArg,
+        /// Binary encoding format (default: base64)
+        #[arg(long, value_enum)]
+        binary: Option<BinaryEncodingArg>,
 
         /// Disable trailing commas
         #[arg(long)]
@@ -74,13 +91,33 @@ enum Commands {
         #[arg(long)]
         no_zulu: bool,
 
-        /// Timestamp precision for fractional seconds
-        #[arg(long, value_enum, default_value = "auto")]
-        timestamp_precision: TimestampPrecisionArg,
+        /// Timestamp precision for fractional seconds (default: auto)
+        #[arg(long, value_enum)]
+        timestamp_precision: Option<TimestampPrecisionArg>,
 
         /// Check if file is already formatted (exit 1 if not)
         #[arg(long)]
         check_format: bool,
+
+        /// Don't print the message when --check-format finds a difference
+        #[arg(short, long, requires = "check_format")]
+        quiet: bool,
+
+        /// Print a unified diff of the changes instead of the output (exit 1 if there are any)
+        #[arg(long, conflicts_with = "output")]
+        diff: bool,
+
+        /// Print a top-level string without quotes or escapes (like `jq -r`)
+        #[arg(short, long, conflicts_with_all = ["check_format", "diff"])]
+        raw_output: bool,
+
+        /// Prepend TEXT as a comment header (one comment line per line of TEXT)
+        #[arg(long, value_name = "TEXT", conflicts_with = "raw_output")]
+        header: Option<String>,
+
+        /// Name to show in messages when the input is read from stdin
+        #[arg(long, value_name = "NAME")]
+        stdin_filename: Option<String>,
     },
 
     /// Check JASN syntax
@@ -97,6 +134,18 @@ enum Commands {
         /// Suppress success messages, only show errors
         #[arg(short, long)]
         quiet: bool,
+
+        /// Print the raw grammar parse tree (for debugging the grammar)
+        #[arg(long, hide = true)]
+        parse_tree: bool,
+
+        /// Warn about suspicious constructs (duplicate keys, deep nesting, mixed quotes, ...)
+        #[arg(long)]
+        lint: bool,
+
+        /// Name to show in messages when the input is read from stdin
+        #[arg(long, value_name = "NAME")]
+        stdin_filename: Option<String>,
     },
 
     /// Generate shell completions
@@ -107,6 +156,21 @@ enum Commands {
     },
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum IndentCharArg {
+    Space,
+    Tab,
+}
+
+impl IndentCharArg {
+    fn indent(self, count: usize) -> Indent {
+        match self {
+            IndentCharArg::Space => Indent::Spaces(count),
+            IndentCharArg::Tab => Indent::Tabs(count),
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
 enum QuoteStyleArg {
     Double,
@@ -167,8 +231,11 @@ fn main() {
         Commands::Format {
             input,
             output,
+            config,
             compact,
             indent,
+            indent_count,
+            indent_char,
             quotes,
             binary,
             no_trailing_commas,
@@ -179,11 +246,17 @@ fn main() {
             no_zulu,
             timestamp_precision,
             check_format,
+            quiet,
+            diff,
+            raw_output,
+            header,
+            stdin_filename,
         } => cmd_fmt(
             input,
             output,
+            config,
             compact,
-            indent,
+            resolve_indent(indent, indent_count, indent_char),
             quotes,
             binary,
             no_trailing_commas,
@@ -194,12 +267,27 @@ fn main() {
             no_zulu,
             timestamp_precision,
             check_format,
+            quiet,
+            diff,
+            raw_output,
+            header.as_deref(),
+            stdin_filename.as_deref(),
         ),
         Commands::Check {
             files,
             verbose,
             quiet,
-        } => cmd_valid(files, verbose, quiet),
+            lint,
+            parse_tree,
+            stdin_filename,
+        } => cmd_valid(
+            files,
+            verbose,
+            quiet,
+            lint,
+            parse_tree,
+            stdin_filename.as_deref(),
+        ),
         Commands::Completions { shell } => {
             cmd_completions(shell);
             Ok(())
@@ -212,62 +300,115 @@ fn main() {
     }
 }
 
+fn resolve_indent(
+    indent: Option<String>,
+    count: Option<usize>,
+    ch: IndentCharArg,
+) -> Option<String> {
+    match count {
+        Some(n) => Some(ch.indent(n).to_string()),
+        None => indent,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn build_format_options(
+    config: Option<&Path>,
     compact: bool,
-    indent: String,
-    quotes: QuoteStyleArg,
-    binary: BinaryEncodingArg,
+    indent: Option<String>,
+    quotes: Option<QuoteStyleArg>,
+    binary: Option<BinaryEncodingArg>,
     no_trailing_commas: bool,
     quote_keys: bool,
     leading_plus: bool,
     no_sort_keys: bool,
     escape_unicode: bool,
     no_zulu: bool,
-    timestamp_precision: TimestampPrecisionArg,
-) -> Options {
-    let base = if compact {
-        Options::compact()
-    } else {
-        Options::pretty().with_indent(indent)
+    timestamp_precision: Option<TimestampPrecisionArg>,
+) -> Result<Options> {
+    let mut opts = match config {
+        Some(path) => load_config(path)?,
+        None => Options::pretty(),
     };
 
-    base.with_quote_style(quotes.into())
-        .with_binary_encoding(binary.into())
-        .with_trailing_commas(!no_trailing_commas)
-        .with_unquoted_keys(!quote_keys)
-        .with_leading_plus(leading_plus)
-        .with_sort_keys(!no_sort_keys)
-        .with_escape_unicode(escape_unicode)
-        .with_use_zulu(!no_zulu)
-        .with_timestamp_precision(timestamp_precision.into())
+    // Only flags given on the command line override the base options
+    if compact {
+        // Compact output stays comma-minimal
+        opts = opts.with_indent("").with_trailing_commas(false);
+    } else if let Some(indent) = indent {
+        opts = opts.with_indent(indent);
+    }
+    if let Some(quotes) = quotes {
+        opts = opts.with_quote_style(quotes.into());
+    }
+    if let Some(binary) = binary {
+        opts = opts.with_binary_encoding(binary.into());
+    }
+    if no_trailing_commas {
+        opts = opts.with_trailing_commas(false);
+    }
+    if quote_keys {
+        opts = opts.with_unquoted_keys(false);
+    }
+    if leading_plus {
+        opts = opts.with_leading_plus(true);
+    }
+    if no_sort_keys {
+        opts = opts.with_sort_keys(false);
+    }
+    if escape_unicode {
+        opts = opts.with_escape_unicode(true);
+    }
+    if no_zulu {
+        opts = opts.with_use_zulu(false);
+    }
+    if let Some(precision) = timestamp_precision {
+        opts = opts.with_timestamp_precision(precision.into());
+    }
+
+    Ok(opts)
+}
+
+fn load_config(path: &Path) -> Result<Options> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    jasn::from_str(&content).with_context(|| format!("Invalid config file: {}", path.display()))
 }
 
 #[allow(clippy::too_many_arguments)]
 fn cmd_fmt(
     input: Option<PathBuf>,
     output: Option<PathBuf>,
+    config: Option<PathBuf>,
     compact: bool,
-    indent: String,
-    quotes: QuoteStyleArg,
-    binary: BinaryEncodingArg,
+    indent: Option<String>,
+    quotes: Option<QuoteStyleArg>,
+    binary: Option<BinaryEncodingArg>,
     no_trailing_commas: bool,
     quote_keys: bool,
     leading_plus: bool,
     no_sort_keys: bool,
     escape_unicode: bool,
     no_zulu: bool,
-    timestamp_precision: TimestampPrecisionArg,
+    timestamp_precision: Option<TimestampPrecisionArg>,
     check_format: bool,
+    quiet: bool,
+    diff: bool,
+    raw_output: bool,
+    header: Option<&str>,
+    stdin_filename: Option<&str>,
 ) -> Result<()> {
     // Read input
     let input_content = read_input(input.as_deref())?;
+    let name = display_name(input.as_deref(), stdin_filename);
 
     // Parse JASN
-    let value = parse(&input_content).context("Failed to parse JASN")?;
+    let value =
+        parse(&input_content).with_context(|| format!("Failed to parse JASN from {name}"))?;
 
     // Build formatting options
     let opts = build_format_options(
+        config.as_deref(),
         compact,
         indent,
         quotes,
@@ -279,27 +420,46 @@ fn cmd_fmt(
         escape_unicode,
         no_zulu,
         timestamp_precision,
-    );
+    )?;
 
     // Format
-    let formatted = format_with_opts(&value, &opts);
+    let formatted = try_format_with_opts(&value, &opts).context("Failed to format JASN")?;
+    let formatted = match header {
+        Some(header) => header_comment(header) + &formatted,
+        None => formatted,
+    };
 
-    // Check mode: compare and exit
-    if check_format {
-        check_formatting(&input_content, &formatted, input.as_deref());
+    // Check and diff modes: compare and exit
+    if check_format || diff {
+        if diff {
+            print_diff(&input_content, &formatted, name);
+        }
+        check_formatting(&input_content, &formatted, name, quiet || !check_format);
         return Ok(());
     }
 
-    // Write output
-    write_output(output.as_deref(), &formatted)?;
+    // Write output, unquoted for a top-level string in raw mode
+    match &value {
+        Value::String(s) if raw_output => write_output(output.as_deref(), s)?,
+        _ => write_output(output.as_deref(), &formatted)?,
+    }
 
     Ok(())
 }
 
-fn cmd_valid(files: Vec<PathBuf>, verbose: bool, quiet: bool) -> Result<()> {
+fn cmd_valid(
+    files: Vec<PathBuf>,
+    verbose: bool,
+    quiet: bool,
+    lint: bool,
+    parse_tree: bool,
+    stdin_filename: Option<&str>,
+) -> Result<()> {
     if files.is_empty() {
         // Read from stdin
-        return validate_file(None, verbose, quiet);
+        let name = display_name(None, stdin_filename);
+        return validate_file(None, name, verbose, quiet, lint, parse_tree)
+            .with_context(|| name.to_string());
     }
 
     let mut all_valid = true;
@@ -307,15 +467,20 @@ fn cmd_valid(files: Vec<PathBuf>, verbose: bool, quiet: bool) -> Result<()> {
 
     for file in &files {
         let file_path = parse_file_arg(file);
+        let shown = match (file_path, stdin_filename) {
+            (None, Some(name)) => name.to_string(),
+            _ => file.display().to_string(),
+        };
+        let name = display_name(file_path, stdin_filename);
 
-        match validate_file(file_path, verbose, quiet) {
+        match validate_file(file_path, name, verbose, quiet, lint, parse_tree) {
             Ok(()) => {
                 if !quiet {
-                    println!("✓ {}", file.display());
+                    println!("✓ {}", shown);
                 }
             }
             Err(e) => {
-                eprintln!("✗ {}: {:#}", file.display(), e);
+                eprintln!("✗ {}: {:#}", shown, e);
                 all_valid = false;
                 error_count += 1;
             }
@@ -332,9 +497,28 @@ fn cmd_valid(files: Vec<PathBuf>, verbose: bool, quiet: bool) -> Result<()> {
     Ok(())
 }
 
-fn validate_file(path: Option<&Path>, verbose: bool, quiet: bool) -> Result<()> {
+fn validate_file(
+    path: Option<&Path>,
+    name: &str,
+    verbose: bool,
+    quiet: bool,
+    lint: bool,
+    parse_tree: bool,
+) -> Result<()> {
     let content = read_input(path)?;
-    let value = parse(&content).context("Invalid JASN syntax")?;
+
+    if parse_tree {
+        let tree = jasn::parser::parse_tree(&content).context("Invalid JASN syntax")?;
+        print!("{}", tree);
+    }
+
+    if lint {
+        for warning in jasn::parser::lint(&content) {
+            eprintln!("warning: {}:{}", name, warning);
+        }
+    }
+
+    let value = parse(&content).map_err(|e| syntax_error(&content, e))?;
 
     if verbose {
         println!("Valid JASN: {:#?}", value);
@@ -345,16 +529,63 @@ fn validate_file(path: Option<&Path>, verbose: bool, quiet: bool) -> Result<()>
     Ok(())
 }
 
-fn check_formatting(input: &str, formatted: &str, path: Option<&Path>) {
+/// Describes a parse error, pointing a caret at its position when it has one.
+fn syntax_error(content: &str, err: jasn::parser::Error) -> anyhow::Error {
+    let Some((line, column)) = err.line_col() else {
+        return anyhow::Error::new(err).context("Invalid JASN syntax");
+    };
+    anyhow!(
+        "Invalid JASN syntax at {}:{}: {}\n{}",
+        line,
+        column,
+        err.message(),
+        caret_snippet(content, line, column)
+    )
+}
+
+/// Renders line `line` of `content` with a `^` under the 1-based character `column`.
+fn caret_snippet(content: &str, line: usize, column: usize) -> String {
+    let text = content.lines().nth(line - 1).unwrap_or_default();
+    // Copy tabs so the caret lines up however the terminal renders them
+    let padding: String = text
+        .chars()
+        .take(column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let gutter = " ".repeat(line.to_string().len());
+    format!("{gutter} |\n{line} | {text}\n{gutter} | {padding}^")
+}
+
+/// Exits with status 1 if `input` differs from `formatted`; returns (status 0) if it matches.
+fn check_formatting(input: &str, formatted: &str, name: &str, quiet: bool) {
     if input.trim() != formatted.trim() {
-        let name = display_name(path);
-        eprintln!("File '{}' is not formatted correctly", name);
+        if !quiet {
+            eprintln!("File '{}' is not formatted correctly", name);
+        }
         process::exit(1);
     }
 }
 
-fn display_name(path: Option<&Path>) -> &str {
-    path.and_then(|p| p.to_str()).unwrap_or("stdin")
+/// Prints a unified diff from `input` to `formatted` (ending in one newline), unless
+/// [`check_formatting`] would accept `input`.
+fn print_diff(input: &str, formatted: &str, name: &str) {
+    if input.trim() == formatted.trim() {
+        return;
+    }
+    let formatted = format!("{}\n", formatted.trim_end_matches('\n'));
+    let diff = TextDiff::from_lines(input, &formatted);
+    print!(
+        "{}",
+        diff.unified_diff().header(&format!("{name}.orig"), name)
+    );
+}
+
+/// Name of an input for messages: its path, or `--stdin-filename` (default `stdin`) for stdin.
+fn display_name<'a>(path: Option<&'a Path>, stdin_filename: Option<&'a str>) -> &'a str {
+    match path {
+        Some(p) if p.to_str() != Some("-") => p.to_str().unwrap_or("stdin"),
+        _ => stdin_filename.unwrap_or("stdin"),
+    }
 }
 
 fn read_input(path: Option<&Path>) -> Result<String> {
@@ -20,6 +20,15 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Deserialize a JASN string into a Rust value.
+///
+/// A document with no value (empty, or only whitespace and comments) fails with
+/// [`parser::Error::EmptyDocument`], even when `T` is an [`Option`]; it does not deserialize as
+/// `None`.
+///
+/// ```
+/// let err = jasn::from_str::<Option<i64>>("").unwrap_err();
+/// assert!(matches!(err, jasn::de::Error::ParseError(jasn::parser::Error::EmptyDocument)));
+/// ```
 pub fn from_str<T>(s: &str) -> Result<T>
 where
     T: for<'de> Deserialize<'de>,
@@ -28,7 +37,28 @@ where
     Ok(de::from_value(&value)?)
 }
 
+/// Deserialize a JASN string into a Rust value, parsing it with `opts`.
+///
+/// Use this to apply the same limits and extensions as
+/// [`parse_with_opts`](parser::parse_with_opts), such as a nesting limit for untrusted input.
+///
+/// ```
+/// use jasn::parser::ParseOptions;
+///
+/// let opts = ParseOptions::new().with_max_depth(Some(1));
+/// assert!(jasn::de::from_str_with_opts::<Vec<Vec<i64>>>("[[1]]", &opts).is_err());
+/// ```
+pub fn from_str_with_opts<T>(s: &str, opts: &parser::ParseOptions) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let value = parser::parse_with_opts(s, opts)?;
+    Ok(de::from_value(&value)?)
+}
+
 /// Deserialize a JASN [`Value`] into a Rust value.
+///
+/// Borrowed fields (`&str`, `#[serde(borrow)]` `Cow<str>`, ...) point into `value`.
 pub fn from_value<'de, T>(value: &'de Value) -> Result<T>
 where
     T: Deserialize<'de>,
@@ -0,0 +1,42 @@
+//! Serde helpers for [`std::time::Duration`] as fractional seconds.
+//!
+//! By default serde represents a `Duration` as a `{secs, nanos}` map. Use this module with
+//! `#[serde(with = "jasn::duration")]` to write it as a single float number of seconds instead.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "jasn::duration")]
+//!     timeout: Duration,
+//! }
+//!
+//! let config = Config { timeout: Duration::from_millis(1500) };
+//! assert_eq!(jasn::to_string(&config).unwrap(), "{timeout:1.5}");
+//! ```
+//!
+//! Sub-nanosecond precision is lost for very long durations, since seconds are stored as [`f64`].
+
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serializer, de::Error as _};
+
+/// Serialize a [`Duration`] as a float number of seconds.
+pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+/// Deserialize a [`Duration`] from a number of seconds (float or integer).
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let secs = f64::deserialize(deserializer)?;
+    Duration::try_from_secs_f64(secs).map_err(D::Error::custom)
+}
@@ -15,70 +15,423 @@
 //! let formatted = format_with_opts(&value, &opts);
 //! ```
 
-use std::collections::BTreeMap;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
 
 use time::{format_description, macros::format_description as fd};
 
 use crate::{Binary, Value};
 
+mod error;
+mod json;
 /// Formatting options and configuration.
 mod options;
-pub use options::{BinaryEncoding, Options, QuoteStyle, TimestampPrecision};
+pub use error::{Error, Result};
+pub use json::{to_json_string, to_json_string_pretty};
+pub use options::{
+    BinaryEncoding, EmptyCollectionStyle, FormatContext, Indent, Options, QuoteStyle, ScalarHook,
+    SortKeys, TimestampPrecision,
+};
 
 /// Formats a JASN [`Value`] into a compact string (no unnecessary whitespace).
 pub fn format(value: &Value) -> String {
-    format_impl(value, &Options::compact(), 0)
+    format_with_opts(value, &Options::compact())
 }
 
 /// Formats a JASN [`Value`] into a pretty-printed string with indentation and newlines.
 pub fn format_pretty(value: &Value) -> String {
-    format_impl(value, &Options::pretty(), 0)
+    format_with_opts(value, &Options::pretty())
 }
 
 /// Formats a JASN [`Value`] with custom formatting options.
+///
+/// This never fails and does not check [`Options::require_ascii`], [`Options::max_output_size`]
+/// or [`Options::validate`]; use [`try_format_with_opts()`] to enforce them.
 pub fn format_with_opts(value: &Value, opts: &Options) -> String {
-    format_impl(value, opts, 0)
+    let mut formatter = Formatter::new(opts, None);
+    formatter.write(value, 0, "");
+    formatter.out
 }
 
-fn format_impl(value: &Value, opts: &Options, depth: usize) -> String {
-    match value {
-        Value::Null => "null".to_string(),
-        Value::Bool(b) => b.to_string(),
-        Value::Int(i) => format_int(*i, opts),
-        Value::Float(f) => format_float(*f, opts),
-        Value::String(s) => {
-            let quote = match opts.quote_style {
-                QuoteStyle::Double => '"',
-                QuoteStyle::Single => '\'',
-                QuoteStyle::PreferDouble => {
-                    if s.contains('"') && !s.contains('\'') {
-                        '\''
-                    } else {
-                        '"'
-                    }
-                }
-            };
-            format_string(s, quote, opts.escape_unicode)
+/// Formats a JASN [`Value`] with custom formatting options, enforcing [`Options::require_ascii`]
+/// and [`Options::max_output_size`].
+///
+/// The options are checked with [`Options::validate`] first. Formatting stops as soon as the
+/// output grows past the size limit, so a huge tree is not rendered in full before failing.
+///
+/// ```
+/// use jasn::{Value, formatter::{Options, try_format_with_opts}};
+///
+/// let value = Value::from("café");
+/// let opts = Options::compact().with_escape_unicode(false).with_require_ascii(true);
+/// assert!(try_format_with_opts(&value, &opts).is_err());
+///
+/// let opts = opts.with_escape_unicode(true);
+/// assert_eq!(try_format_with_opts(&value, &opts).unwrap(), r#""caf\u00e9""#);
+/// ```
+pub fn try_format_with_opts(value: &Value, opts: &Options) -> Result<String> {
+    opts.validate()?;
+    let mut formatter = Formatter::new(opts, opts.max_output_size);
+    formatter.write(value, 0, "");
+    let formatted = formatter.finish()?;
+    if opts.require_ascii
+        && let Some(ch) = formatted.chars().find(|ch| !ch.is_ascii())
+    {
+        return Err(Error::NonAscii(ch));
+    }
+    Ok(formatted)
+}
+
+/// Formats a JASN [`Value`] like [`try_format_with_opts()`], then parses the output back and
+/// checks that it matches `value` (using [`Value::structural_eq`], so `NaN` matches `NaN`).
+///
+/// A safety net for catching formatter bugs while debugging or in tests; it costs a full parse.
+/// Options that intentionally drop information, such as a coarser
+/// [`TimestampPrecision`], are reported as [`Error::RoundTripMismatch`].
+///
+/// ```
+/// use jasn::{Value, formatter::{Options, to_string_checked}};
+///
+/// let value = Value::from([("ratio", f64::NAN)]);
+/// assert_eq!(to_string_checked(&value, &Options::compact()).unwrap(), "{ratio:nan}");
+/// ```
+pub fn to_string_checked(value: &Value, opts: &Options) -> Result<String> {
+    let formatted = try_format_with_opts(value, opts)?;
+    let reparsed = crate::parse(&formatted).map_err(Error::Unparseable)?;
+    if !reparsed.structural_eq(value) {
+        return Err(Error::RoundTripMismatch);
+    }
+    Ok(formatted)
+}
+
+/// Formats a JASN [`Value`] preceded by `header` as a block comment.
+///
+/// Useful for generated files ("Generated by X, do not edit"). The parser skips comments, so
+/// the header does not affect the parsed value. See [`header_comment()`] for the exact layout.
+///
+/// ```
+/// use jasn::{Value, formatter::{Options, to_string_with_header}};
+///
+/// let text = to_string_with_header(&Value::Int(1), "Generated file", &Options::compact());
+/// assert_eq!(text, "/*\n * Generated file\n */\n1");
+/// assert_eq!(jasn::parse(&text).unwrap(), Value::Int(1));
+/// ```
+pub fn to_string_with_header(value: &Value, header: &str, opts: &Options) -> String {
+    header_comment(header) + &format_with_opts(value, opts)
+}
+
+/// Renders `header` as a JASN block comment, one ` * ` line per header line.
+///
+/// JASN only has `/* */` comments, so any `*/` inside the header is written as `* /` to keep
+/// the comment from ending early. The result ends with a newline.
+pub fn header_comment(header: &str) -> String {
+    let mut out = String::from("/*\n");
+    for line in header.lines() {
+        let line = format!(" * {}", line.replace("*/", "* /"));
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out.push_str(" */\n");
+    out
+}
+
+/// Formats one value into a single output buffer.
+struct Formatter<'a> {
+    opts: &'a Options,
+    out: String,
+    /// Formatting stops once `out` is longer than this many bytes.
+    limit: Option<usize>,
+    /// [`Options::scalar_hook`] results from single-line attempts that were dropped, by path,
+    /// so the final layout reuses them and each hook call happens once.
+    hooked: HashMap<String, Option<String>>,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(opts: &'a Options, limit: Option<usize>) -> Self {
+        Self {
+            opts,
+            out: String::new(),
+            limit,
+            hooked: HashMap::new(),
         }
-        Value::Binary(b) => format_binary(b, opts.binary_encoding),
-        Value::Timestamp(t) => format_timestamp(t, opts),
-        Value::List(items) => {
-            if opts.indent.is_empty() {
-                format_list_compact(items, opts)
-            } else {
-                format_list_pretty(items, opts, depth)
+    }
+
+    /// Returns the output, or an error if it outgrew the limit (and was cut short).
+    fn finish(self) -> Result<String> {
+        match self.limit {
+            Some(limit) if self.out.len() > limit => Err(Error::OutputTooLarge(limit)),
+            _ => Ok(self.out),
+        }
+    }
+
+    /// Whether the output is past the limit, after which remaining items are skipped.
+    fn exceeded(&self) -> bool {
+        self.limit.is_some_and(|limit| self.out.len() > limit)
+    }
+
+    /// Writes `value`, which sits `depth` levels deep at the JSON Pointer `path`. The path is
+    /// only tracked while [`Options::scalar_hook`] is set, and is empty otherwise.
+    fn write(&mut self, value: &Value, depth: usize, path: &str) {
+        let compact = self.opts.indent.is_empty();
+        match value {
+            Value::List(items) if compact => self.write_list_compact(items, path),
+            Value::List(items) => self.write_list_pretty(value, items, depth, path),
+            Value::Map(map) if compact => self.write_map_compact(map, path),
+            Value::Map(map) => self.write_map_pretty(value, map, depth, path),
+            _ => {
+                let text = match self.hook(value, path, false) {
+                    Some(custom) => custom,
+                    None => scalar_text(value, self.opts, Some(depth)).unwrap_or_default(),
+                };
+                self.out.push_str(&text);
             }
         }
-        Value::Map(map) => {
-            if opts.indent.is_empty() {
-                format_map_compact(map, opts)
-            } else {
-                format_map_pretty(map, opts, depth)
+    }
+
+    /// Runs [`Options::scalar_hook`] on the scalar at `path`, at most once per path.
+    ///
+    /// A `tentative` call comes from a single-line attempt that may be dropped, so its result
+    /// is remembered for the layout that is finally written.
+    fn hook(&mut self, value: &Value, path: &str, tentative: bool) -> Option<String> {
+        let opts = self.opts;
+        let hook = opts.scalar_hook.as_ref()?;
+        let call = || hook.call(value, &FormatContext { path });
+        if tentative {
+            self.hooked
+                .entry(path.to_string())
+                .or_insert_with(call)
+                .clone()
+        } else {
+            self.hooked.remove(path).unwrap_or_else(call)
+        }
+    }
+
+    fn write_list_compact(&mut self, items: &[Value], path: &str) {
+        self.out.push('[');
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.out.push(',');
+            }
+            self.write(item, 0, &child_path(path, i, self.opts));
+            if self.exceeded() {
+                return;
+            }
+        }
+        if self.opts.trailing_commas && !items.is_empty() {
+            self.out.push(',');
+        }
+        self.out.push(']');
+    }
+
+    fn write_list_pretty(&mut self, value: &Value, items: &[Value], depth: usize, path: &str) {
+        let indent = self.opts.indent.repeat(depth);
+        if items.is_empty() {
+            self.out
+                .push_str(&empty_collection("[", "]", &indent, self.opts));
+            return;
+        }
+        if self.write_inline_if_fits(value, &indent, path) {
+            return;
+        }
+
+        let item_indent = self.opts.indent.repeat(depth + 1);
+        self.out.push_str("[\n");
+        for (i, item) in items.iter().enumerate() {
+            self.out.push_str(&item_indent);
+            self.write(item, depth + 1, &child_path(path, i, self.opts));
+            if i < items.len() - 1 || self.opts.trailing_commas {
+                self.out.push(',');
+            }
+            self.out.push('\n');
+            if self.exceeded() {
+                return;
             }
         }
+        self.out.push_str(&indent);
+        self.out.push(']');
+    }
+
+    fn write_map_compact(&mut self, map: &BTreeMap<String, Value>, path: &str) {
+        self.out.push('{');
+        for (i, (key, value)) in sorted_entries(map, self.opts.sort_keys).iter().enumerate() {
+            if i > 0 {
+                self.out.push(',');
+            }
+            self.out.push_str(&format_key(key, self.opts));
+            self.out.push(':');
+            self.write(value, 0, &child_path(path, key, self.opts));
+            if self.exceeded() {
+                return;
+            }
+        }
+        if self.opts.trailing_commas && !map.is_empty() {
+            self.out.push(',');
+        }
+        self.out.push('}');
+    }
+
+    fn write_map_pretty(
+        &mut self,
+        value: &Value,
+        map: &BTreeMap<String, Value>,
+        depth: usize,
+        path: &str,
+    ) {
+        let indent = self.opts.indent.repeat(depth);
+        if map.is_empty() {
+            self.out
+                .push_str(&empty_collection("{", "}", &indent, self.opts));
+            return;
+        }
+        if self.write_inline_if_fits(value, &indent, path) {
+            return;
+        }
+
+        let item_indent = self.opts.indent.repeat(depth + 1);
+        self.out.push_str("{\n");
+        let entries = sorted_entries(map, self.opts.sort_keys);
+        for (i, (key, value)) in entries.iter().enumerate() {
+            self.out.push_str(&item_indent);
+            self.out.push_str(&format_key(key, self.opts));
+            self.out.push_str(": ");
+            self.write(value, depth + 1, &child_path(path, key, self.opts));
+            if i < entries.len() - 1 || self.opts.trailing_commas {
+                self.out.push(',');
+            }
+            self.out.push('\n');
+            if self.exceeded() {
+                return;
+            }
+        }
+        self.out.push_str(&indent);
+        self.out.push('}');
+    }
+
+    /// Writes the single-line form of a collection if it fits within [`Options::max_width`]
+    /// after `indent`, returning whether it did.
+    ///
+    /// Rendering the line stops as soon as it is too wide, so trying a large collection costs
+    /// no more than the width.
+    fn write_inline_if_fits(&mut self, value: &Value, indent: &str, path: &str) -> bool {
+        if self.opts.max_width == 0 {
+            return false;
+        }
+        let Some(width) = self.opts.max_width.checked_sub(indent.chars().count()) else {
+            return false;
+        };
+        let mut line = Inline {
+            text: String::new(),
+            width,
+            max_bytes: self.limit.map(|limit| limit.saturating_sub(self.out.len())),
+        };
+        if !self.write_inline(value, path, &mut line) {
+            return false;
+        }
+        self.out.push_str(&line.text);
+        if !self.hooked.is_empty() {
+            // The kept line used these results, so nothing will ask for them again
+            let prefix = format!("{path}/");
+            self.hooked.retain(|key, _| !key.starts_with(&prefix));
+        }
+        true
+    }
+
+    /// Appends the single-line form of `value` to `line`, returning `false` as soon as the line
+    /// outgrows its limit.
+    fn write_inline(&mut self, value: &Value, path: &str, line: &mut Inline) -> bool {
+        match value {
+            Value::List(items) => {
+                line.push("[")
+                    && items.iter().enumerate().all(|(i, item)| {
+                        (i == 0 || line.push(", "))
+                            && self.write_inline(item, &child_path(path, i, self.opts), line)
+                    })
+                    && line.push("]")
+            }
+            Value::Map(map) if map.is_empty() => line.push("{}"),
+            Value::Map(map) => {
+                let entries = sorted_entries(map, self.opts.sort_keys);
+                line.push("{ ")
+                    && entries.iter().enumerate().all(|(i, (key, value))| {
+                        (i == 0 || line.push(", "))
+                            && line.push(&format_key(key, self.opts))
+                            && line.push(": ")
+                            && self.write_inline(value, &child_path(path, key, self.opts), line)
+                    })
+                    && line.push(" }")
+            }
+            _ => {
+                let text = match self.hook(value, path, true) {
+                    Some(custom) => custom,
+                    // Inline binary is never wrapped across lines
+                    None => scalar_text(value, self.opts, None).unwrap_or_default(),
+                };
+                line.push(&text)
+            }
+        }
+    }
+}
+
+/// A single line of output under construction, given up once it is wider than `width`
+/// characters or longer than `max_bytes`.
+struct Inline {
+    text: String,
+    /// Characters still available on the line.
+    width: usize,
+    max_bytes: Option<usize>,
+}
+
+impl Inline {
+    /// Appends `s`, or returns `false` if that would pass either limit.
+    fn push(&mut self, s: &str) -> bool {
+        let chars = s.chars().count();
+        if chars > self.width
+            || self
+                .max_bytes
+                .is_some_and(|max| self.text.len() + s.len() > max)
+        {
+            return false;
+        }
+        self.width -= chars;
+        self.text.push_str(s);
+        true
     }
 }
 
+/// Renders a scalar with the default formatting, wrapping binary data at `wrap_depth` when
+/// [`Options::binary_line_wrap`] applies. Returns `None` for lists and maps.
+fn scalar_text(value: &Value, opts: &Options, wrap_depth: Option<usize>) -> Option<String> {
+    Some(match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Int(i) => format_int(*i, opts),
+        Value::Float(f) => format_float(*f, opts),
+        Value::String(s) => format_string(s, quote_char(s, opts.quote_style), opts.escape_unicode),
+        Value::Binary(b) => match (opts.binary_line_wrap, wrap_depth) {
+            (Some(width), Some(depth)) if width > 0 && !opts.indent.is_empty() => {
+                format_binary_wrapped(b, opts, depth, width)
+            }
+            _ => format_binary(b, opts.binary_encoding),
+        },
+        Value::Timestamp(t) => format_timestamp(t, opts),
+        Value::Duration(d) => format!("dur\"{}\"", jasn_core::duration::to_iso8601(d)),
+        Value::List(_) | Value::Map(_) => return None,
+    })
+}
+
+/// Appends a key or index to a JSON Pointer, escaping `~` and `/`. Returns an empty path when
+/// no [`Options::scalar_hook`] would read it.
+fn child_path(path: &str, segment: impl Display, opts: &Options) -> String {
+    if opts.scalar_hook.is_none() {
+        return String::new();
+    }
+    let segment = segment.to_string().replace('~', "~0").replace('/', "~1");
+    format!("{path}/{segment}")
+}
+
 fn format_int(i: i64, opts: &Options) -> String {
     if opts.leading_plus && i >= 0 {
         format!("+{}", i)
@@ -100,7 +453,14 @@ fn format_float(f: f64, opts: &Options) -> String {
         // Ensure we always have a decimal point to distinguish from integers
         format!("{:.1}", f)
     } else {
-        f.to_string()
+        // Whole floats from 1e15 up display without a decimal point, which would read back
+        // as an integer, so those use exponent notation
+        let s = f.to_string();
+        if s.contains('.') {
+            s
+        } else {
+            format!("{:e}", f)
+        }
     };
 
     // Add leading plus for positive numbers (including +inf, but not nan)
@@ -128,6 +488,11 @@ const TIMESTAMP_FORMAT_NANOS: &[format_description::FormatItem<'static>] = fd!(
 );
 
 fn format_timestamp(t: &crate::Timestamp, opts: &Options) -> String {
+    format!("ts\"{}\"", timestamp_text(t, opts))
+}
+
+/// Renders a timestamp as RFC 3339 text, without the `ts"..."` wrapper.
+fn timestamp_text(t: &crate::Timestamp, opts: &Options) -> String {
     // Select format descriptor based on precision
     let format: &[format_description::FormatItem<'_>] = match opts.timestamp_precision {
         TimestampPrecision::Auto => {
@@ -145,7 +510,7 @@ fn format_timestamp(t: &crate::Timestamp, opts: &Options) -> String {
             } else {
                 formatted
             };
-            return format!("ts\"{}\"", final_str);
+            return final_str;
         }
         TimestampPrecision::Seconds => TIMESTAMP_FORMAT_SECONDS,
         TimestampPrecision::Milliseconds => TIMESTAMP_FORMAT_MILLIS,
@@ -155,16 +520,14 @@ fn format_timestamp(t: &crate::Timestamp, opts: &Options) -> String {
 
     // Custom formats output +00:00, convert to Z if needed
     let formatted = t.format(format).unwrap_or_else(|_| t.to_string());
-    let final_str = if opts.use_zulu && formatted.ends_with("+00:00") {
+    if opts.use_zulu && formatted.ends_with("+00:00") {
         let mut s = formatted;
         s.truncate(s.len() - 6);
         s.push('Z');
         s
     } else {
         formatted
-    };
-
-    format!("ts\"{}\"", final_str)
+    }
 }
 
 fn format_string(s: &str, quote: char, escape_unicode: bool) -> String {
@@ -182,7 +545,8 @@ fn format_string(s: &str, quote: char, escape_unicode: bool) -> String {
             '\r' => result.push_str("\\r"),
             '\x08' => result.push_str("\\b"),
             '\x0C' => result.push_str("\\f"),
-            c if c.is_control() => {
+            // U+2028/U+2029 end a line in JavaScript, so they are escaped like control characters
+            c if c.is_control() || matches!(c, '\u{2028}' | '\u{2029}') => {
                 use std::fmt::Write;
                 write!(&mut result, "\\u{:04x}", c as u32).unwrap();
             }
@@ -208,142 +572,141 @@ fn format_string(s: &str, quote: char, escape_unicode: bool) -> String {
     result
 }
 
-fn format_binary(binary: &Binary, encoding: BinaryEncoding) -> String {
+/// Returns the literal prefix and encoded content for `binary`.
+pub(crate) fn encode_binary(binary: &Binary, encoding: BinaryEncoding) -> (&'static str, String) {
     match encoding {
         BinaryEncoding::Base64 => {
             use base64::{Engine as _, engine::general_purpose};
-            let encoded = general_purpose::STANDARD.encode(&binary.0);
-            format!("b64\"{}\"", encoded)
-        }
-        BinaryEncoding::Hex => {
-            let hex: String = binary.0.iter().map(|b| format!("{:02x}", b)).collect();
-            format!("hex\"{}\"", hex)
+            ("b64", general_purpose::STANDARD.encode(&binary.0))
         }
+        BinaryEncoding::Hex => (
+            "hex",
+            binary.0.iter().map(|b| format!("{:02x}", b)).collect(),
+        ),
     }
 }
 
-fn format_list_compact(items: &[Value], opts: &Options) -> String {
-    if items.is_empty() {
-        return "[]".to_string();
-    }
-
-    let formatted: Vec<String> = items
-        .iter()
-        .map(|item| format_impl(item, opts, 0))
-        .collect();
-    format!("[{}]", formatted.join(","))
+fn format_binary(binary: &Binary, encoding: BinaryEncoding) -> String {
+    let (prefix, encoded) = encode_binary(binary, encoding);
+    format!("{prefix}\"{encoded}\"")
 }
 
-fn format_list_pretty(items: &[Value], opts: &Options, depth: usize) -> String {
-    if items.is_empty() {
-        return "[]".to_string();
+fn format_binary_wrapped(binary: &Binary, opts: &Options, depth: usize, width: usize) -> String {
+    let (prefix, encoded) = encode_binary(binary, opts.binary_encoding);
+    if encoded.len() <= width {
+        return format!("{prefix}\"{encoded}\"");
     }
 
     let indent = opts.indent.repeat(depth);
-    let item_indent = opts.indent.repeat(depth + 1);
-    let mut result = String::from("[\n");
-
-    for (i, item) in items.iter().enumerate() {
-        result.push_str(&item_indent);
-        result.push_str(&format_impl(item, opts, depth + 1));
-        if i < items.len() - 1 || opts.trailing_commas {
-            result.push(',');
-        }
+    let line_indent = opts.indent.repeat(depth + 1);
+    let mut result = format!("{prefix}\"\n");
+    // The encoding is ASCII, so byte chunks are whole characters
+    for line in encoded.as_bytes().chunks(width) {
+        result.push_str(&line_indent);
+        result.push_str(std::str::from_utf8(line).expect("encoded binary is ASCII"));
         result.push('\n');
     }
-
     result.push_str(&indent);
-    result.push(']');
+    result.push('"');
     result
 }
 
-fn format_map_compact(map: &BTreeMap<String, Value>, opts: &Options) -> String {
-    if map.is_empty() {
-        return "{}".to_string();
+fn empty_collection(open: &str, close: &str, indent: &str, opts: &Options) -> String {
+    match opts.empty_collection_style {
+        EmptyCollectionStyle::Inline => format!("{open}{close}"),
+        EmptyCollectionStyle::Multiline => format!("{open}\n{indent}{close}"),
     }
-
-    let entries: Vec<_> = if opts.sort_keys {
-        let mut sorted: Vec<_> = map.iter().collect();
-        sorted.sort_by_key(|(k, _)| *k);
-        sorted
-    } else {
-        map.iter().collect()
-    };
-
-    let formatted: Vec<String> = entries
-        .iter()
-        .map(|(k, v)| {
-            let key_str = if opts.unquoted_keys && can_be_unquoted(k) {
-                k.to_string()
-            } else {
-                let quote = match opts.quote_style {
-                    QuoteStyle::Double => '"',
-                    QuoteStyle::Single => '\'',
-                    QuoteStyle::PreferDouble => {
-                        if k.contains('"') && !k.contains('\'') {
-                            '\''
-                        } else {
-                            '"'
-                        }
-                    }
-                };
-                format_string(k, quote, opts.escape_unicode)
-            };
-            format!("{}:{}", key_str, format_impl(v, opts, 0))
-        })
-        .collect();
-    format!("{{{}}}", formatted.join(","))
 }
 
-fn format_map_pretty(map: &BTreeMap<String, Value>, opts: &Options, depth: usize) -> String {
-    if map.is_empty() {
-        return "{}".to_string();
+fn sorted_entries(map: &BTreeMap<String, Value>, mode: SortKeys) -> Vec<(&String, &Value)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    match mode {
+        SortKeys::Off => {}
+        SortKeys::Lexicographic => entries.sort_by_key(|(key, _)| *key),
+        SortKeys::CaseInsensitive => entries.sort_by(|(a, _), (b, _)| {
+            let folded = a.bytes().map(|c| c.to_ascii_lowercase());
+            folded
+                .cmp(b.bytes().map(|c| c.to_ascii_lowercase()))
+                .then_with(|| a.cmp(b))
+        }),
+        SortKeys::Natural => {
+            entries.sort_by(|(a, _), (b, _)| natural_cmp(a, b).then_with(|| a.cmp(b)))
+        }
     }
+    entries
+}
 
-    let indent = opts.indent.repeat(depth);
-    let item_indent = opts.indent.repeat(depth + 1);
-    let mut result = String::from("{\n");
-
-    let entries: Vec<_> = if opts.sort_keys {
-        let mut sorted: Vec<_> = map.iter().collect();
-        sorted.sort_by_key(|(k, _)| *k);
-        sorted
-    } else {
-        map.iter().collect()
-    };
-    for (i, (key, value)) in entries.iter().enumerate() {
-        result.push_str(&item_indent);
-
-        // Format key (possibly unquoted)
-        if opts.unquoted_keys && can_be_unquoted(key) {
-            result.push_str(key);
+/// Compares strings ignoring ASCII case, treating each run of digits as a single number.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(ca), Some(cb)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+
+        if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let (num_a, rest_a) = split_digits(a);
+            let (num_b, rest_b) = split_digits(b);
+            // Without leading zeros, a longer run is a larger number
+            let ordering = num_a.len().cmp(&num_b.len()).then_with(|| num_a.cmp(num_b));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            (a, b) = (rest_a, rest_b);
         } else {
-            let quote = match opts.quote_style {
-                QuoteStyle::Double => '"',
-                QuoteStyle::Single => '\'',
-                QuoteStyle::PreferDouble => {
-                    if key.contains('"') && !key.contains('\'') {
-                        '\''
-                    } else {
-                        '"'
-                    }
-                }
-            };
-            result.push_str(&format_string(key, quote, opts.escape_unicode));
+            let ordering = ca.to_ascii_lowercase().cmp(&cb.to_ascii_lowercase());
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            (a, b) = (&a[ca.len_utf8()..], &b[cb.len_utf8()..]);
         }
+    }
+}
 
-        result.push_str(": ");
-        result.push_str(&format_impl(value, opts, depth + 1));
+/// Splits a leading run of ASCII digits off `s`, returning it without leading zeros.
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (digits, rest) = s.split_at(end);
+    (digits.trim_start_matches('0'), rest)
+}
 
-        if i < entries.len() - 1 || opts.trailing_commas {
-            result.push(',');
+/// Formats a map key, leaving it unquoted when allowed.
+fn format_key(key: &str, opts: &Options) -> String {
+    if opts.unquoted_keys
+        && can_be_unquoted(key)
+        && !(opts.quote_keyword_like_keys && is_keyword_ignore_case(key))
+    {
+        key.to_string()
+    } else {
+        format_string(
+            key,
+            quote_char(key, opts.key_quote_style),
+            opts.escape_unicode,
+        )
+    }
+}
+
+fn quote_char(s: &str, style: QuoteStyle) -> char {
+    match style {
+        QuoteStyle::Double => '"',
+        QuoteStyle::Single => '\'',
+        QuoteStyle::PreferDouble => {
+            if s.contains('"') && !s.contains('\'') {
+                '\''
+            } else {
+                '"'
+            }
         }
-        result.push('\n');
     }
+}
 
-    result.push_str(&indent);
-    result.push('}');
-    result
+/// Keywords the parser recognizes where a value is expected. Matching is case-sensitive, and
+/// in key position the parser always reads a bare word as an identifier, so these are quoted
+/// only to keep the output unambiguous to read.
+const KEYWORDS: [&str; 5] = ["null", "true", "false", "inf", "nan"];
+
+fn is_keyword_ignore_case(key: &str) -> bool {
+    KEYWORDS.iter().any(|kw| kw.eq_ignore_ascii_case(key))
 }
 
 fn can_be_unquoted(key: &str) -> bool {
@@ -352,7 +715,7 @@ fn can_be_unquoted(key: &str) -> bool {
     }
 
     // Reserved keywords cannot be unquoted
-    if matches!(key, "null" | "true" | "false" | "inf" | "nan") {
+    if KEYWORDS.contains(&key) {
         return false;
     }
 
@@ -375,6 +738,21 @@ mod tests {
     use super::*;
     use crate::parse;
 
+    #[test]
+    fn test_to_string_with_header() {
+        let value = Value::from([("a", 1)]);
+        let text = to_string_with_header(
+            &value,
+            "Generated by X, do not edit\n\nSee */docs",
+            &Options::pretty(),
+        );
+        assert_eq!(
+            text,
+            "/*\n * Generated by X, do not edit\n *\n * See * /docs\n */\n{\n  a: 1,\n}"
+        );
+        assert_eq!(parse(&text).unwrap(), value);
+    }
+
     #[rstest]
     #[case(Value::Null, "null")]
     #[case(Value::Bool(true), "true")]
@@ -390,6 +768,9 @@ mod tests {
     #[case(2.5, "2.5")]
     #[case(f64::INFINITY, "inf")]
     #[case(f64::NEG_INFINITY, "-inf")]
+    #[case(1e15, "1e15")]
+    #[case(-1e300, "-1e300")]
+    #[case(123456789012345678.0, "1.2345678901234568e17")]
     fn test_format_float(#[case] value: f64, #[case] expected: &str) {
         assert_eq!(format(&Value::Float(value)), expected);
     }
@@ -418,6 +799,65 @@ mod tests {
         assert_eq!(format(&Value::Binary(binary)), "b64\"SGVsbG8=\"");
     }
 
+    #[rstest]
+    #[case(BinaryEncoding::Base64, "b64\"\n    SGVs\n    bG8=\n  \"")]
+    #[case(BinaryEncoding::Hex, "hex\"\n    4865\n    6c6c\n    6f\n  \"")]
+    fn test_format_binary_wrapped(#[case] encoding: BinaryEncoding, #[case] expected: &str) {
+        let value = Value::from([("data", Value::Binary(Binary(b"Hello".to_vec())))]);
+        let opts = Options::pretty()
+            .with_binary_encoding(encoding)
+            .with_binary_line_wrap(Some(4));
+        assert_eq!(
+            format_with_opts(&value, &opts),
+            format!("{{\n  data: {expected},\n}}")
+        );
+    }
+
+    #[rstest]
+    #[case(Options::pretty().with_binary_line_wrap(Some(8)))]
+    #[case(Options::pretty().with_binary_line_wrap(Some(0)))]
+    #[case(Options::compact().with_binary_line_wrap(Some(4)))]
+    fn test_format_binary_not_wrapped(#[case] opts: Options) {
+        let value = Value::Binary(Binary(b"Hello".to_vec()));
+        assert_eq!(format_with_opts(&value, &opts), "b64\"SGVsbG8=\"");
+    }
+
+    #[rstest]
+    #[case(Options::compact())]
+    #[case(Options::pretty())]
+    #[case(Options::pretty().with_max_width(80).with_binary_line_wrap(Some(4)))]
+    fn test_to_string_checked(#[case] opts: Options) {
+        let value = Value::from([
+            ("nan", Value::Float(f64::NAN)),
+            ("inf", Value::Float(f64::NEG_INFINITY)),
+            ("path", Value::from("a/b\\c")),
+            ("data", Value::Binary(Binary(b"Hello".to_vec()))),
+            ("list", Value::from([1, 2])),
+        ]);
+        let formatted = to_string_checked(&value, &opts).unwrap();
+        assert_eq!(formatted, format_with_opts(&value, &opts));
+    }
+
+    #[test]
+    fn test_to_string_checked_lossy_options() {
+        let ts = crate::Timestamp::from_unix_timestamp_nanos(1_234_567_890_123_456_789).unwrap();
+        let value = Value::Timestamp(ts);
+        let opts = Options::compact().with_timestamp_precision(TimestampPrecision::Seconds);
+        assert!(matches!(
+            to_string_checked(&value, &opts),
+            Err(Error::RoundTripMismatch)
+        ));
+
+        // Options errors surface before the round-trip
+        let opts = Options::compact()
+            .with_require_ascii(true)
+            .with_escape_unicode(false);
+        assert!(matches!(
+            to_string_checked(&Value::from("é"), &opts),
+            Err(Error::NonAscii('é'))
+        ));
+    }
+
     #[test]
     fn test_format_list() {
         let list = vec![Value::Int(1), Value::Int(2), Value::Int(3)];
@@ -471,6 +911,44 @@ mod tests {
         assert_eq!(parse(&format(&map_val)).unwrap(), map_val);
     }
 
+    /// String values are always quoted, so a string that looks like another type never
+    /// re-parses as that type.
+    #[rstest]
+    fn test_string_values_always_quoted(
+        #[values(
+            "true",
+            "false",
+            "null",
+            "undefined",
+            "42",
+            "-7",
+            "0x1f",
+            "3.14",
+            "1e5",
+            "inf",
+            "-inf",
+            "nan",
+            "",
+            "hello"
+        )]
+        text: &str,
+        #[values(Options::compact(), Options::pretty(), Options::compact().with_quote_style(QuoteStyle::Single))]
+        opts: Options,
+    ) {
+        let value = Value::String(text.to_string());
+        let formatted = format_with_opts(&value, &opts);
+        assert!(
+            formatted.starts_with(['"', '\'']) && formatted.ends_with(['"', '\'']),
+            "{text:?} formatted as {formatted}"
+        );
+        assert_eq!(parse(&formatted).unwrap(), value);
+
+        // Also as a map value and list item, where unquoted keys are in play
+        let nested = Value::from([(text, Value::from([value.clone()]))]);
+        let formatted = format_with_opts(&nested, &opts.clone().with_unquoted_keys(true));
+        assert_eq!(parse(&formatted).unwrap(), nested);
+    }
+
     #[test]
     fn test_pretty_format() {
         let mut map = BTreeMap::new();
@@ -493,10 +971,36 @@ mod tests {
     #[case("true", false)]
     #[case("false", false)]
     #[case("kebab-case", false)]
+    #[case("inf", false)]
+    #[case("nan", false)]
+    #[case("NaN", true)]
+    #[case("Inf", true)]
+    #[case("True", true)]
     fn test_can_be_unquoted(#[case] input: &str, #[case] expected: bool) {
         assert_eq!(can_be_unquoted(input), expected);
     }
 
+    #[test]
+    fn test_keyword_like_keys() {
+        let value = Value::from([
+            ("NaN", 1),
+            ("Inf", 2),
+            ("True", 3),
+            ("NULL", 4),
+            ("Infinity", 5),
+        ]);
+
+        // Keywords are lowercase-only, so other cases round-trip as plain identifiers
+        let text = format(&value);
+        assert_eq!(text, "{Inf:2,Infinity:5,NULL:4,NaN:1,True:3}");
+        assert_eq!(parse(&text).unwrap(), value);
+
+        let opts = Options::compact().with_quote_keyword_like_keys(true);
+        let text = format_with_opts(&value, &opts);
+        assert_eq!(text, r#"{"Inf":2,Infinity:5,"NULL":4,"NaN":1,"True":3}"#);
+        assert_eq!(parse(&text).unwrap(), value);
+    }
+
     #[rstest]
     #[case(Value::Int(42), "+42")]
     #[case(Value::Int(0), "+0")]
@@ -542,6 +1046,352 @@ mod tests {
         assert!(result.find("a").unwrap() < result.find("z").unwrap());
     }
 
+    #[rstest]
+    #[case(false, "[1,2,3]", "{a:1,b:[]}")]
+    #[case(true, "[1,2,3,]", "{a:1,b:[],}")]
+    fn test_compact_trailing_commas(#[case] enable: bool, #[case] list: &str, #[case] map: &str) {
+        let opts = Options::compact().with_trailing_commas(enable);
+
+        let value = Value::from([1, 2, 3]);
+        assert_eq!(format_with_opts(&value, &opts), list);
+        assert_eq!(crate::parse(list).unwrap(), value);
+
+        let value = Value::from([("a", Value::Int(1)), ("b", Value::List(vec![]))]);
+        assert_eq!(format_with_opts(&value, &opts), map);
+        assert_eq!(crate::parse(map).unwrap(), value);
+    }
+
+    #[rstest]
+    #[case(SortKeys::Off, "{Item10:1,Item2:2,item1:3}")]
+    #[case(SortKeys::Lexicographic, "{Item10:1,Item2:2,item1:3}")]
+    #[case(SortKeys::CaseInsensitive, "{item1:3,Item10:1,Item2:2}")]
+    #[case(SortKeys::Natural, "{item1:3,Item2:2,Item10:1}")]
+    fn test_sort_keys_modes(#[case] mode: SortKeys, #[case] expected: &str) {
+        let value = Value::from([("Item2", 2), ("Item10", 1), ("item1", 3)]);
+        let opts = Options::compact().with_sort_keys_mode(mode);
+        assert_eq!(format_with_opts(&value, &opts), expected);
+    }
+
+    #[test]
+    fn test_trailing_comma_after_nested_structures() {
+        let value = Value::from([
+            Value::Int(1),
+            Value::from([("a", Value::from([1, 2])), ("b", Value::from([("c", 3)]))]),
+        ]);
+        let text = format_with_opts(&value, &Options::pretty().with_trailing_commas(true));
+        assert_eq!(
+            text,
+            "[\n  1,\n  {\n    a: [\n      1,\n      2,\n    ],\n    b: {\n      c: 3,\n    },\n  },\n]"
+        );
+        assert_eq!(parse(&text).unwrap(), value);
+
+        let text = format_with_opts(&value, &Options::pretty().with_trailing_commas(false));
+        assert!(text.ends_with("      c: 3\n    }\n  }\n]"));
+        assert_eq!(parse(&text).unwrap(), value);
+    }
+
+    #[rstest]
+    #[case(EmptyCollectionStyle::Inline, Value::List(vec![]), "[]")]
+    #[case(EmptyCollectionStyle::Inline, Value::Map(BTreeMap::new()), "{}")]
+    #[case(EmptyCollectionStyle::Multiline, Value::List(vec![]), "[\n]")]
+    #[case(EmptyCollectionStyle::Multiline, Value::Map(BTreeMap::new()), "{\n}")]
+    #[case(
+        EmptyCollectionStyle::Inline,
+        Value::from([("a", Value::List(vec![])), ("b", Value::Map(BTreeMap::new()))]),
+        "{\n  a: [],\n  b: {},\n}"
+    )]
+    #[case(
+        EmptyCollectionStyle::Multiline,
+        Value::from([("a", Value::List(vec![])), ("b", Value::Map(BTreeMap::new()))]),
+        "{\n  a: [\n  ],\n  b: {\n  },\n}"
+    )]
+    #[case(
+        EmptyCollectionStyle::Multiline,
+        Value::from([Value::from([Value::List(vec![])])]),
+        "[\n  [\n    [\n    ],\n  ],\n]"
+    )]
+    fn test_empty_collection_style(
+        #[case] style: EmptyCollectionStyle,
+        #[case] value: Value,
+        #[case] expected: &str,
+    ) {
+        let opts = Options::pretty().with_empty_collection_style(style);
+        let text = format_with_opts(&value, &opts);
+        assert_eq!(text, expected);
+        assert_eq!(parse(&text).unwrap(), value);
+    }
+
+    #[test]
+    fn test_empty_collection_style_ignored_in_compact() {
+        let value = Value::from([("a", Value::List(vec![]))]);
+        let opts = Options::compact().with_empty_collection_style(EmptyCollectionStyle::Multiline);
+        assert_eq!(format_with_opts(&value, &opts), "{a:[]}");
+    }
+
+    #[rstest]
+    #[case(Options::compact(), "{retries:3,timeout:500ms}")]
+    #[case(Options::pretty(), "{\n  retries: 3,\n  timeout: 500ms,\n}")]
+    #[case(Options::pretty().with_max_width(80), "{ retries: 3, timeout: 500ms }")]
+    fn test_scalar_hook(#[case] opts: Options, #[case] expected: &str) {
+        let value = Value::from([("timeout", 500), ("retries", 3)]);
+        let opts = opts.with_scalar_hook(|value, ctx| match (value, ctx.path()) {
+            (Value::Int(ms), "/timeout") => Some(format!("{ms}ms")),
+            _ => None,
+        });
+        assert_eq!(format_with_opts(&value, &opts), expected);
+    }
+
+    #[rstest]
+    #[case(Options::compact())]
+    #[case(Options::pretty())]
+    #[case(Options::pretty().with_max_width(80))]
+    fn test_scalar_hook_paths(#[case] opts: Options) {
+        let value = Value::from([
+            ("a/b", Value::from([1, 2])),
+            ("c~d", Value::from([("e", Value::Binary(Binary(vec![1])))])),
+            ("f", Value::List(vec![])),
+        ]);
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let opts = opts.with_scalar_hook({
+            let seen = seen.clone();
+            move |_, ctx| {
+                seen.lock().unwrap().push(ctx.path().to_string());
+                None
+            }
+        });
+        let formatted = format_with_opts(&value, &opts);
+        assert_eq!(
+            formatted,
+            format_with_opts(
+                &value,
+                &Options {
+                    scalar_hook: None,
+                    ..opts
+                }
+            )
+        );
+        assert_eq!(*seen.lock().unwrap(), ["/a~1b/0", "/a~1b/1", "/c~0d/e"]);
+        for path in seen.lock().unwrap().iter() {
+            assert!(value.pointer(path).is_some(), "{path}");
+        }
+    }
+
+    #[rstest]
+    #[case(12)]
+    #[case(20)]
+    #[case(40)]
+    fn test_scalar_hook_once_per_path(#[case] max_width: usize) {
+        let value = Value::from([
+            (
+                "a",
+                Value::from([("b", Value::from([1, 2])), ("c", Value::from("text"))]),
+            ),
+            ("d", Value::from([Value::from(3), Value::from([4, 5])])),
+        ]);
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let opts = Options::pretty()
+            .with_max_width(max_width)
+            .with_scalar_hook({
+                let seen = seen.clone();
+                move |value, ctx| {
+                    seen.lock().unwrap().push(ctx.path().to_string());
+                    value.as_int().map(|i| format!("{i}ms"))
+                }
+            });
+        let formatted = format_with_opts(&value, &opts);
+        assert!(formatted.contains("5ms"), "{formatted}");
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(
+            seen,
+            ["/a/b/0", "/a/b/1", "/a/c", "/d/0", "/d/1/0", "/d/1/1"]
+        );
+    }
+
+    #[test]
+    fn test_scalar_hook_root() {
+        let opts = Options::compact().with_scalar_hook(|_, ctx| Some(format!("<{}>", ctx.path())));
+        assert_eq!(format_with_opts(&Value::Null, &opts), "<>");
+        assert_eq!(format_with_opts(&Value::from([1]), &opts), "[</0>]");
+    }
+
+    #[test]
+    fn test_max_output_size() {
+        let row = Value::from([
+            ("id", Value::Int(1)),
+            ("tags", Value::from(["a", "b", "c"])),
+        ]);
+        let value = Value::List(vec![row; 10_000]);
+
+        for opts in [
+            Options::compact(),
+            Options::pretty(),
+            Options::pretty().with_max_width(80),
+        ] {
+            let opts = opts.with_max_output_size(Some(1024));
+            assert!(matches!(
+                try_format_with_opts(&value, &opts),
+                Err(Error::OutputTooLarge(1024))
+            ));
+
+            // The infallible formatter ignores the limit
+            assert!(format_with_opts(&value, &opts).len() > 1024);
+        }
+
+        let small = Value::from([1, 2, 3]);
+        let opts = Options::compact().with_max_output_size(Some(7));
+        assert_eq!(try_format_with_opts(&small, &opts).unwrap(), "[1,2,3]");
+        let opts = Options::compact().with_max_output_size(Some(6));
+        assert!(try_format_with_opts(&small, &opts).is_err());
+    }
+
+    #[rstest]
+    #[case(Options::compact())]
+    #[case(Options::pretty())]
+    #[case(Options::pretty().with_max_width(usize::MAX))]
+    fn test_max_output_size_stops_early(#[case] opts: Options) {
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering::Relaxed},
+        };
+
+        let value = Value::List(vec![Value::Int(1); 100_000]);
+        let rendered = Arc::new(AtomicUsize::new(0));
+        let opts = opts.with_max_output_size(Some(100)).with_scalar_hook({
+            let rendered = rendered.clone();
+            move |_, _| {
+                rendered.fetch_add(1, Relaxed);
+                None
+            }
+        });
+        assert!(matches!(
+            try_format_with_opts(&value, &opts),
+            Err(Error::OutputTooLarge(100))
+        ));
+        // Only the items up to the limit were rendered
+        let rendered = rendered.load(Relaxed);
+        assert!(rendered < 200, "{rendered} items rendered");
+    }
+
+    #[test]
+    fn test_max_width_stops_rendering_wide_collections() {
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering::Relaxed},
+        };
+
+        let row = Value::from([("id", Value::Int(1)), ("tags", Value::from(["a", "b"]))]);
+        let value = Value::List(vec![row; 10_000]);
+        let rendered = Arc::new(AtomicUsize::new(0));
+        let opts = Options::pretty().with_max_width(80).with_scalar_hook({
+            let rendered = rendered.clone();
+            move |_, _| {
+                rendered.fetch_add(1, Relaxed);
+                None
+            }
+        });
+        let text = format_with_opts(&value, &opts);
+        assert!(text.starts_with("[\n  { id: 1, tags: [\"a\", \"b\"] },\n"));
+        // Each row's three scalars, plus the few that fit before the root gave up on one line
+        let rendered = rendered.load(Relaxed);
+        assert!(rendered < 30_100, "{rendered} scalars rendered");
+    }
+
+    #[test]
+    fn test_max_width_inlines_small_collections() {
+        let value = Value::from([
+            ("point", Value::from([("x", 1), ("y", 2)])),
+            ("tags", Value::from(["a", "b"])),
+            (
+                "large",
+                Value::from([("first", "some long text"), ("second", "more long text")]),
+            ),
+        ]);
+        let opts = Options::pretty().with_max_width(30);
+        let text = format_with_opts(&value, &opts);
+        assert_eq!(
+            text,
+            "{\n  large: {\n    first: \"some long text\",\n    second: \"more long text\",\n  },\n  point: { x: 1, y: 2 },\n  tags: [\"a\", \"b\"],\n}"
+        );
+        assert_eq!(parse(&text).unwrap(), value);
+
+        let small = Value::from([("x", Value::Int(1)), ("y", Value::from([1, 2]))]);
+        assert_eq!(format_with_opts(&small, &opts), "{ x: 1, y: [1, 2] }");
+        assert_eq!(
+            format_with_opts(&small, &Options::pretty()),
+            "{\n  x: 1,\n  y: [\n    1,\n    2,\n  ],\n}"
+        );
+    }
+
+    #[rstest]
+    #[case(SortKeys::Off)]
+    #[case(SortKeys::Lexicographic)]
+    #[case(SortKeys::CaseInsensitive)]
+    #[case(SortKeys::Natural)]
+    fn test_sort_keys_compact_matches_pretty(#[case] mode: SortKeys) {
+        let value = Value::from([("b", 1), ("Item10", 2), ("a", 3), ("item2", 4)]);
+        let key_order = |opts: Options| {
+            let Value::Map(map) = &value else {
+                unreachable!()
+            };
+            let text = format_with_opts(&value, &opts.with_sort_keys_mode(mode));
+            let mut keys: Vec<&str> = map.keys().map(String::as_str).collect();
+            keys.sort_by_key(|key| text.find(&format!("{key}:")).unwrap());
+            keys
+        };
+        assert_eq!(key_order(Options::compact()), key_order(Options::pretty()));
+    }
+
+    #[rstest]
+    #[case("a2", "a10", Ordering::Less)]
+    #[case("a02", "a2", Ordering::Equal)]
+    #[case("a", "a0", Ordering::Less)]
+    #[case("x9y", "X9Z", Ordering::Less)]
+    #[case("v1.10", "v1.9", Ordering::Greater)]
+    #[case("99999999999999999999999", "100000000000000000000000", Ordering::Less)]
+    fn test_natural_cmp(#[case] a: &str, #[case] b: &str, #[case] expected: Ordering) {
+        assert_eq!(natural_cmp(a, b), expected);
+    }
+
+    #[test]
+    fn test_key_quote_style() {
+        let value = Value::from([("my key", "value"), ("plain", "text")]);
+
+        let opts = Options::compact()
+            .with_quote_style(QuoteStyle::Single)
+            .with_key_quote_style(QuoteStyle::Double);
+        assert_eq!(
+            format_with_opts(&value, &opts),
+            r#"{"my key":'value',plain:'text'}"#
+        );
+
+        let opts = opts.with_unquoted_keys(false);
+        assert_eq!(
+            format_with_opts(&value, &opts),
+            r#"{"my key":'value',"plain":'text'}"#
+        );
+
+        let opts = Options::pretty()
+            .with_quote_style(QuoteStyle::Single)
+            .with_key_quote_style(QuoteStyle::Double);
+        assert_eq!(
+            format_with_opts(&value, &opts),
+            "{\n  \"my key\": 'value',\n  plain: 'text',\n}"
+        );
+    }
+
+    #[test]
+    fn test_compact_keeps_unicode_raw() {
+        let value = Value::from([("greeting", "你好 🌍 café")]);
+        let formatted = format(&value);
+        assert_eq!(formatted, "{greeting:\"你好 🌍 café\"}");
+        assert!(
+            formatted.len()
+                < format_with_opts(&value, &Options::compact().with_escape_unicode(true)).len()
+        );
+        assert_eq!(crate::parse(&formatted).unwrap(), value);
+    }
+
     #[test]
     fn test_escape_unicode() {
         let opts = Options::compact().with_escape_unicode(true);
@@ -568,6 +1418,38 @@ mod tests {
         assert_eq!(result, "\"café\"");
     }
 
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_line_separators_always_escaped(#[case] escape_unicode: bool) {
+        let opts = Options::compact().with_escape_unicode(escape_unicode);
+        let value = Value::String("a\u{2028}b\u{2029}c".to_string());
+        let formatted = format_with_opts(&value, &opts);
+        assert_eq!(formatted, "\"a\\u2028b\\u2029c\"");
+        assert_eq!(crate::parse(&formatted).unwrap(), value);
+    }
+
+    #[rstest]
+    #[case(false, "café", false)]
+    #[case(true, "café", true)]
+    #[case(false, "cafe", true)]
+    fn test_require_ascii(#[case] escape: bool, #[case] input: &str, #[case] ok: bool) {
+        let opts = Options::compact()
+            .with_escape_unicode(escape)
+            .with_require_ascii(true);
+        let value = Value::from([("name", input)]);
+
+        let result = try_format_with_opts(&value, &opts);
+        assert_eq!(result.is_ok(), ok);
+        if let Err(err) = result {
+            assert!(matches!(err, Error::NonAscii('é')));
+        }
+
+        // Without require_ascii, non-ASCII is emitted literally
+        let opts = opts.with_require_ascii(false);
+        assert!(try_format_with_opts(&value, &opts).is_ok());
+    }
+
     #[rstest]
     #[case("😀", "\"\\ud83d\\ude00\"")]
     #[case("👍", "\"\\ud83d\\udc4d\"")]
@@ -601,6 +1483,20 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case("dur\"PT1H30M\"", "dur\"PT1H30M\"")]
+    #[case("dur\"PT90M\"", "dur\"PT1H30M\"")]
+    #[case("dur\"P1DT2H\"", "dur\"P1DT2H\"")]
+    #[case("dur\"P2W\"", "dur\"P14D\"")]
+    #[case("dur\"PT0,25S\"", "dur\"PT0.25S\"")]
+    #[case("dur\"P0D\"", "dur\"PT0S\"")]
+    #[case("dur\"PT0.000000001S\"", "dur\"PT0.000000001S\"")]
+    fn test_format_duration_round_trip(#[case] input: &str, #[case] expected: &str) {
+        let value = parse(input).unwrap();
+        assert_eq!(format(&value), expected);
+        assert_eq!(parse(expected).unwrap(), value);
+    }
+
     #[test]
     fn test_format_timestamp_default() {
         use crate::Timestamp;
@@ -0,0 +1,43 @@
+/// Errors that can occur during formatting.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A non-ASCII character would be emitted literally while [`Options::require_ascii`] is set.
+    ///
+    /// [`Options::require_ascii`]: super::Options::require_ascii
+    #[error("Non-ASCII character {0:?} in output (enable escape_unicode to escape it)")]
+    NonAscii(char),
+
+    /// [`Options::indent`] is not made up solely of spaces or solely of tabs.
+    ///
+    /// [`Options::indent`]: super::Options::indent
+    #[error("Invalid indent {0:?} (must be only spaces or only tabs)")]
+    InvalidIndent(String),
+
+    /// The output grew past [`Options::max_output_size`] bytes.
+    ///
+    /// [`Options::max_output_size`]: super::Options::max_output_size
+    #[error("Formatted output exceeds the limit of {0} bytes")]
+    OutputTooLarge(usize),
+
+    /// A `nan` or infinite float was passed to [`to_json_string`], which JSON cannot represent.
+    ///
+    /// [`to_json_string`]: super::to_json_string
+    #[error("Float {0} has no JSON representation")]
+    NonFiniteFloat(f64),
+
+    /// The output of [`to_string_checked`] could not be parsed back.
+    ///
+    /// [`to_string_checked`]: super::to_string_checked
+    #[error("Formatted output does not parse: {0}")]
+    Unparseable(#[source] crate::parser::Error),
+
+    /// The output of [`to_string_checked`] parsed to a different value.
+    ///
+    /// [`to_string_checked`]: super::to_string_checked
+    #[error("Formatted output does not parse back to the same value")]
+    RoundTripMismatch,
+}
+
+/// Result type for formatting operations.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,81 @@
+use super::{BinaryEncoding, Error, Options, QuoteStyle, Result, try_format_with_opts};
+use crate::{Value, ValueExt};
+
+/// Formats a [`Value`] as compact JSON.
+///
+/// Keys are always quoted and no trailing commas are written. Binary data becomes a base64
+/// string, timestamps RFC 3339 strings and durations ISO 8601 strings, so those types do not
+/// survive a round trip.
+/// Fails with [`Error::NonFiniteFloat`] for `inf` and `nan`, which JSON cannot represent.
+///
+/// ```
+/// use jasn::{Binary, Value};
+///
+/// let value = Value::from([("data", Value::from(Binary::from(b"hi"))), ("n", Value::Int(1))]);
+/// assert_eq!(jasn::to_json_string(&value).unwrap(), r#"{"data":"aGk=","n":1}"#);
+/// ```
+pub fn to_json_string(value: &Value) -> Result<String> {
+    to_json_with_opts(value, Options::compact())
+}
+
+/// Formats a [`Value`] as indented JSON; see [`to_json_string`].
+pub fn to_json_string_pretty(value: &Value) -> Result<String> {
+    to_json_with_opts(value, Options::pretty())
+}
+
+fn to_json_with_opts(value: &Value, base: Options) -> Result<String> {
+    let opts = base
+        .with_trailing_commas(false)
+        .with_quote_style(QuoteStyle::Double)
+        .with_key_quote_style(QuoteStyle::Double)
+        .with_unquoted_keys(false)
+        .with_leading_plus(false);
+    let mut json = value.clone();
+    json.stringify_rich_types(BinaryEncoding::Base64);
+    if let Some(f) = non_finite_float(&json) {
+        return Err(Error::NonFiniteFloat(f));
+    }
+    try_format_with_opts(&json, &opts)
+}
+
+/// Returns the first `inf` or `nan` in the tree, which JSON has no syntax for.
+fn non_finite_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(f) if !f.is_finite() => Some(*f),
+        Value::List(items) => items.iter().find_map(non_finite_float),
+        Value::Map(map) => map.values().find_map(non_finite_float),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(f64::NAN)]
+    #[case(f64::INFINITY)]
+    #[case(f64::NEG_INFINITY)]
+    fn test_non_finite_floats_rejected(#[case] f: f64) {
+        let value = Value::from([("list", Value::from([Value::Float(f)]))]);
+        assert!(matches!(
+            to_json_string(&value),
+            Err(Error::NonFiniteFloat(_))
+        ));
+        assert!(matches!(
+            to_json_string_pretty(&value),
+            Err(Error::NonFiniteFloat(_))
+        ));
+    }
+
+    #[test]
+    fn test_json_pretty_has_no_trailing_commas() {
+        let value = Value::from([("a", Value::from([1, 2])), ("b", Value::Int(3))]);
+        assert_eq!(
+            to_json_string_pretty(&value).unwrap(),
+            "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": 3\n}"
+        );
+    }
+}
@@ -1,35 +1,89 @@
+use std::{fmt, str::FromStr, sync::Arc};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Error, Result};
+use crate::Value;
+
 /// Formatting options for JASN output.
-#[derive(Debug, Clone)]
+///
+/// With the `serde` feature, options can be stored as a JASN document (for example a project
+/// formatting config). Fields missing from the document take their [`Options::default`] value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct Options {
     /// Indentation string (e.g., "  " or "\t"). Empty string means compact output.
     pub indent: String,
 
-    /// Add trailing commas to lists and maps.
+    /// In pretty output, keep a list or map on one line (`[1, 2]`, `{ x: 1, y: 2 }`) when its
+    /// one-line form plus the current indentation is at most this many characters (a preceding
+    /// map key is not counted). `0` always breaks non-empty collections across lines.
+    pub max_width: usize,
+
+    /// Add trailing commas to non-empty lists and maps, in both compact and pretty output.
     pub trailing_commas: bool,
 
-    /// Quote style for strings.
+    /// Quote style for string values.
     pub quote_style: QuoteStyle,
 
+    /// Quote style for map keys that need quoting.
+    pub key_quote_style: QuoteStyle,
+
     /// Binary data encoding preference.
     pub binary_encoding: BinaryEncoding,
 
+    /// In pretty output, wrap encoded binary data longer than this many characters across
+    /// lines of at most this width, between a `b64"` line and a closing `"` line. The parser
+    /// ignores whitespace inside binary literals. `None` or `Some(0)` never wraps.
+    pub binary_line_wrap: Option<usize>,
+
     /// Use unquoted keys in maps when possible.
     pub unquoted_keys: bool,
 
+    /// Quote keys that spell a keyword in another letter case, such as `NaN`, `Inf` or `True`.
+    ///
+    /// JASN keywords are lowercase-only, so such keys already round-trip unquoted; this is for
+    /// readers and tools that treat keywords case-insensitively.
+    pub quote_keyword_like_keys: bool,
+
     /// Add leading plus sign to positive numbers (+42, +3.14, +inf).
     pub leading_plus: bool,
 
-    /// Sort map keys alphabetically for consistent output.
-    pub sort_keys: bool,
+    /// Order in which map keys are emitted.
+    pub sort_keys: SortKeys,
+
+    /// How empty lists and maps are written in pretty mode.
+    pub empty_collection_style: EmptyCollectionStyle,
 
     /// Escape all non-ASCII characters as \uXXXX sequences.
     pub escape_unicode: bool,
 
+    /// Fail in [`try_format_with_opts`] if the output would contain non-ASCII characters.
+    ///
+    /// [`try_format_with_opts`]: super::try_format_with_opts
+    pub require_ascii: bool,
+
+    /// Fail in [`try_format_with_opts`] once the output exceeds this many bytes.
+    ///
+    /// Guards against formatting pathologically large trees (for example built from untrusted
+    /// data). `None` means no limit.
+    ///
+    /// [`try_format_with_opts`]: super::try_format_with_opts
+    pub max_output_size: Option<usize>,
+
     /// Use 'Z' for UTC timestamps instead of '+00:00'.
     pub use_zulu: bool,
 
     /// Precision for timestamp fractional seconds.
     pub timestamp_precision: TimestampPrecision,
+
+    /// Callback consulted before each scalar is rendered; see [`ScalarHook`].
+    ///
+    /// Not part of the serialized options.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub scalar_hook: Option<ScalarHook>,
 }
 
 impl Default for Options {
@@ -40,18 +94,30 @@ impl Default for Options {
 
 impl Options {
     /// Creates options for compact output.
+    ///
+    /// Like [`Self::pretty`], non-ASCII text is written as raw UTF-8, which is valid JASN and
+    /// smaller than `\uXXXX` escapes. Add [`with_escape_unicode(true)`](Self::with_escape_unicode)
+    /// for ASCII-only transports.
     pub fn compact() -> Self {
         Self {
             indent: String::new(),
+            max_width: 0,
             trailing_commas: false,
             quote_style: QuoteStyle::Double,
+            key_quote_style: QuoteStyle::Double,
             binary_encoding: BinaryEncoding::Base64,
+            binary_line_wrap: None,
             unquoted_keys: true,
+            quote_keyword_like_keys: false,
             leading_plus: false,
-            sort_keys: false,
-            escape_unicode: true,
+            sort_keys: SortKeys::Off,
+            empty_collection_style: EmptyCollectionStyle::Inline,
+            escape_unicode: false,
+            require_ascii: false,
+            max_output_size: None,
             use_zulu: true,
             timestamp_precision: TimestampPrecision::Auto,
+            scalar_hook: None,
         }
     }
 
@@ -59,33 +125,69 @@ impl Options {
     pub fn pretty() -> Self {
         Self {
             indent: "  ".to_string(),
+            max_width: 0,
             trailing_commas: true,
             quote_style: QuoteStyle::Double,
+            key_quote_style: QuoteStyle::Double,
             binary_encoding: BinaryEncoding::Base64,
+            binary_line_wrap: None,
             unquoted_keys: true,
+            quote_keyword_like_keys: false,
             leading_plus: false,
-            sort_keys: true,
+            sort_keys: SortKeys::Lexicographic,
+            empty_collection_style: EmptyCollectionStyle::Inline,
             escape_unicode: false,
+            require_ascii: false,
+            max_output_size: None,
             use_zulu: true,
             timestamp_precision: TimestampPrecision::Auto,
+            scalar_hook: None,
         }
     }
 
-    /// Sets the indentation string.
+    /// Sets the indentation, either as an [`Indent`] or as a raw string.
+    ///
+    /// Strings are not checked here; use [`Self::validate`] (or [`try_format_with_opts`],
+    /// which calls it) to reject indentation that is not purely spaces or purely tabs.
+    ///
+    /// [`try_format_with_opts`]: super::try_format_with_opts
     pub fn with_indent(mut self, indent: impl Into<String>) -> Self {
         self.indent = indent.into();
         self
     }
 
+    /// Sets the line width up to which pretty output keeps collections inline (`0` disables).
+    pub fn with_max_width(mut self, width: usize) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    /// Checks that the options describe parseable output.
+    ///
+    /// Currently this rejects an [`indent`](Self::indent) that mixes spaces and tabs or contains
+    /// any other character.
+    pub fn validate(&self) -> Result<()> {
+        self.indent.parse::<Indent>().map(|_| ())
+    }
+
     /// Sets whether to use trailing commas.
     pub fn with_trailing_commas(mut self, enable: bool) -> Self {
         self.trailing_commas = enable;
         self
     }
 
-    /// Sets the quote style.
+    /// Sets the quote style for both string values and map keys.
+    ///
+    /// Use [`Self::with_key_quote_style`] afterwards to quote keys differently.
     pub fn with_quote_style(mut self, style: QuoteStyle) -> Self {
         self.quote_style = style;
+        self.key_quote_style = style;
+        self
+    }
+
+    /// Sets the quote style for map keys only.
+    pub fn with_key_quote_style(mut self, style: QuoteStyle) -> Self {
+        self.key_quote_style = style;
         self
     }
 
@@ -95,12 +197,24 @@ impl Options {
         self
     }
 
+    /// Sets the width at which pretty output wraps long binary data (`None` disables).
+    pub fn with_binary_line_wrap(mut self, width: Option<usize>) -> Self {
+        self.binary_line_wrap = width;
+        self
+    }
+
     /// Sets whether to use unquoted keys.
     pub fn with_unquoted_keys(mut self, enable: bool) -> Self {
         self.unquoted_keys = enable;
         self
     }
 
+    /// Sets whether to quote keys that match a keyword ignoring case.
+    pub fn with_quote_keyword_like_keys(mut self, enable: bool) -> Self {
+        self.quote_keyword_like_keys = enable;
+        self
+    }
+
     /// Sets whether to add leading plus sign to positive numbers.
     pub fn with_leading_plus(mut self, enable: bool) -> Self {
         self.leading_plus = enable;
@@ -108,8 +222,27 @@ impl Options {
     }
 
     /// Sets whether to sort map keys alphabetically.
+    ///
+    /// `true` selects [`SortKeys::Lexicographic`] and `false` selects [`SortKeys::Off`]; use
+    /// [`Self::with_sort_keys_mode`] for the other orders.
     pub fn with_sort_keys(mut self, enable: bool) -> Self {
-        self.sort_keys = enable;
+        self.sort_keys = if enable {
+            SortKeys::Lexicographic
+        } else {
+            SortKeys::Off
+        };
+        self
+    }
+
+    /// Sets the order in which map keys are emitted.
+    pub fn with_sort_keys_mode(mut self, mode: SortKeys) -> Self {
+        self.sort_keys = mode;
+        self
+    }
+
+    /// Sets how empty lists and maps are written in pretty mode.
+    pub fn with_empty_collection_style(mut self, style: EmptyCollectionStyle) -> Self {
+        self.empty_collection_style = style;
         self
     }
 
@@ -119,6 +252,20 @@ impl Options {
         self
     }
 
+    /// Sets whether formatting must fail instead of emitting non-ASCII characters.
+    pub fn with_require_ascii(mut self, enable: bool) -> Self {
+        self.require_ascii = enable;
+        self
+    }
+
+    /// Sets the output size limit in bytes enforced by [`try_format_with_opts`].
+    ///
+    /// [`try_format_with_opts`]: super::try_format_with_opts
+    pub fn with_max_output_size(mut self, limit: Option<usize>) -> Self {
+        self.max_output_size = limit;
+        self
+    }
+
     /// Sets whether to use 'Z' for UTC timestamps instead of '+00:00'.
     pub fn with_use_zulu(mut self, enable: bool) -> Self {
         self.use_zulu = enable;
@@ -130,10 +277,140 @@ impl Options {
         self.timestamp_precision = precision;
         self
     }
+
+    /// Sets a callback that can override how individual scalars are rendered.
+    pub fn with_scalar_hook(
+        mut self,
+        hook: impl Fn(&Value, &FormatContext) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.scalar_hook = Some(ScalarHook::new(hook));
+        self
+    }
+}
+
+/// Custom rendering for scalars, set with [`Options::with_scalar_hook`].
+///
+/// The formatter calls the hook for every value that is not a list or map, before its default
+/// rendering. Returning `Some(text)` writes `text` verbatim in place of the value; `None` falls
+/// through to the default. The output is not checked, so a hook that writes something other
+/// than a JASN value produces text that does not parse back.
+///
+/// ```
+/// use jasn::{Value, formatter::{Options, format_with_opts}};
+///
+/// let value = Value::from([("timeout", 500), ("retries", 3)]);
+/// let opts = Options::compact().with_scalar_hook(|value, ctx| match (value, ctx.path()) {
+///     (Value::Int(ms), "/timeout") => Some(format!("{ms}ms")),
+///     _ => None,
+/// });
+/// assert_eq!(format_with_opts(&value, &opts), "{retries:3,timeout:500ms}");
+/// ```
+///
+/// Hooks compare equal only to clones of themselves.
+#[derive(Clone)]
+pub struct ScalarHook(Arc<ScalarHookFn>);
+
+type ScalarHookFn = dyn Fn(&Value, &FormatContext) -> Option<String> + Send + Sync;
+
+impl ScalarHook {
+    /// Wraps a callback as a hook.
+    pub fn new(
+        hook: impl Fn(&Value, &FormatContext) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    pub(crate) fn call(&self, value: &Value, ctx: &FormatContext) -> Option<String> {
+        (self.0)(value, ctx)
+    }
+}
+
+impl fmt::Debug for ScalarHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ScalarHook(..)")
+    }
+}
+
+impl PartialEq for ScalarHook {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ScalarHook {}
+
+/// Where a scalar passed to a [`ScalarHook`] sits in the formatted document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatContext<'a> {
+    pub(crate) path: &'a str,
+}
+
+impl FormatContext<'_> {
+    /// The value's location as a JSON Pointer ([RFC 6901]), such as `/server/ports/0`, in the
+    /// form accepted by [`Value::pointer`]. The root value has the empty path.
+    ///
+    /// [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901
+    pub fn path(&self) -> &str {
+        self.path
+    }
+}
+
+/// Typed indentation for [`Options::with_indent`].
+///
+/// `Spaces(0)` and `Tabs(0)` both render as the empty string, i.e. compact output.
+///
+/// ```
+/// use jasn::formatter::{Indent, Options};
+///
+/// assert_eq!(Indent::Spaces(4).to_string(), "    ");
+/// assert_eq!("\t\t".parse::<Indent>().unwrap(), Indent::Tabs(2));
+/// assert!(" \t".parse::<Indent>().is_err());
+///
+/// let opts = Options::pretty().with_indent(Indent::Tabs(1));
+/// assert_eq!(opts.indent, "\t");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// Indent with the given number of spaces per level.
+    Spaces(usize),
+
+    /// Indent with the given number of tabs per level.
+    Tabs(usize),
+}
+
+impl fmt::Display for Indent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Indent::Spaces(n) => f.write_str(&" ".repeat(n)),
+            Indent::Tabs(n) => f.write_str(&"\t".repeat(n)),
+        }
+    }
+}
+
+impl FromStr for Indent {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.chars().all(|c| c == ' ') {
+            Ok(Indent::Spaces(s.len()))
+        } else if s.chars().all(|c| c == '\t') {
+            Ok(Indent::Tabs(s.len()))
+        } else {
+            Err(Error::InvalidIndent(s.to_string()))
+        }
+    }
+}
+
+impl From<Indent> for String {
+    fn from(indent: Indent) -> Self {
+        indent.to_string()
+    }
 }
 
 /// Quote style for strings and map keys.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum QuoteStyle {
     /// Always use double quotes: "string"
     Double,
@@ -147,6 +424,8 @@ pub enum QuoteStyle {
 
 /// Binary data encoding preference.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum BinaryEncoding {
     /// Always use base64: b64"..."
     Base64,
@@ -155,8 +434,42 @@ pub enum BinaryEncoding {
     Hex,
 }
 
+/// Order in which map keys are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SortKeys {
+    /// Keep the map's own iteration order (which is byte order for [`Value::Map`]).
+    ///
+    /// [`Value::Map`]: crate::Value::Map
+    Off,
+
+    /// Byte-wise order: `Item10`, `Item2`, `item1`.
+    Lexicographic,
+
+    /// Order ignoring ASCII case: `item1`, `Item10`, `Item2`.
+    CaseInsensitive,
+
+    /// Case-insensitive order comparing digit runs by numeric value: `item1`, `Item2`, `Item10`.
+    Natural,
+}
+
+/// How empty lists and maps are written in pretty mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EmptyCollectionStyle {
+    /// On one line: `[]` and `{}` (default).
+    Inline,
+
+    /// Brackets on separate lines, with the closing one at the collection's indentation.
+    Multiline,
+}
+
 /// Precision for timestamp fractional seconds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum TimestampPrecision {
     /// Automatically use minimum necessary digits (default).
     Auto,
@@ -204,5 +517,120 @@ mod tests {
         assert_eq!(opts.indent, "\t");
         assert!(opts.trailing_commas);
         assert_eq!(opts.quote_style, QuoteStyle::Single);
+        assert_eq!(opts.key_quote_style, QuoteStyle::Single);
+
+        let opts = Options::compact()
+            .with_quote_style(QuoteStyle::Single)
+            .with_key_quote_style(QuoteStyle::Double);
+        assert_eq!(opts.quote_style, QuoteStyle::Single);
+        assert_eq!(opts.key_quote_style, QuoteStyle::Double);
+    }
+
+    #[test]
+    fn test_builders_cover_every_field() {
+        let opts = Options::compact()
+            .with_indent(Indent::Tabs(1))
+            .with_max_width(80)
+            .with_trailing_commas(true)
+            .with_quote_style(QuoteStyle::Single)
+            .with_key_quote_style(QuoteStyle::PreferDouble)
+            .with_binary_encoding(BinaryEncoding::Hex)
+            .with_binary_line_wrap(Some(64))
+            .with_unquoted_keys(false)
+            .with_quote_keyword_like_keys(true)
+            .with_leading_plus(true)
+            .with_sort_keys_mode(SortKeys::Natural)
+            .with_empty_collection_style(EmptyCollectionStyle::Multiline)
+            .with_escape_unicode(true)
+            .with_require_ascii(true)
+            .with_max_output_size(Some(1024))
+            .with_use_zulu(false)
+            .with_timestamp_precision(TimestampPrecision::Nanoseconds)
+            .with_scalar_hook(|_, _| None);
+
+        // A struct literal, so a new field fails to compile here until it gets a builder
+        let expected = Options {
+            indent: "\t".to_string(),
+            max_width: 80,
+            trailing_commas: true,
+            quote_style: QuoteStyle::Single,
+            key_quote_style: QuoteStyle::PreferDouble,
+            binary_encoding: BinaryEncoding::Hex,
+            binary_line_wrap: Some(64),
+            unquoted_keys: false,
+            quote_keyword_like_keys: true,
+            leading_plus: true,
+            sort_keys: SortKeys::Natural,
+            empty_collection_style: EmptyCollectionStyle::Multiline,
+            escape_unicode: true,
+            require_ascii: true,
+            max_output_size: Some(1024),
+            use_zulu: false,
+            timestamp_precision: TimestampPrecision::Nanoseconds,
+            scalar_hook: opts.scalar_hook.clone(),
+        };
+        assert_eq!(opts, expected);
+    }
+
+    #[test]
+    fn test_indent_enum() {
+        assert_eq!(
+            Options::compact().with_indent(Indent::Spaces(4)).indent,
+            "    "
+        );
+        assert_eq!(
+            Options::compact().with_indent(Indent::Tabs(2)).indent,
+            "\t\t"
+        );
+        assert_eq!(Options::pretty().with_indent(Indent::Spaces(0)).indent, "");
+    }
+
+    #[test]
+    fn test_indent_parse() {
+        assert_eq!("".parse::<Indent>().unwrap(), Indent::Spaces(0));
+        assert_eq!("   ".parse::<Indent>().unwrap(), Indent::Spaces(3));
+        assert_eq!("\t".parse::<Indent>().unwrap(), Indent::Tabs(1));
+        assert!(matches!(
+            " \t".parse::<Indent>(),
+            Err(Error::InvalidIndent(s)) if s == " \t"
+        ));
+        assert!("x".parse::<Indent>().is_err());
+    }
+
+    #[test]
+    fn test_validate_indent() {
+        assert!(Options::compact().validate().is_ok());
+        assert!(Options::pretty().with_indent("\t").validate().is_ok());
+        assert!(Options::pretty().with_indent("x").validate().is_err());
+        assert!(Options::pretty().with_indent("\t  ").validate().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let opts = Options::compact()
+            .with_quote_style(QuoteStyle::PreferDouble)
+            .with_key_quote_style(QuoteStyle::Single)
+            .with_binary_encoding(BinaryEncoding::Hex)
+            .with_timestamp_precision(TimestampPrecision::Milliseconds);
+
+        let text = crate::to_string(&opts).unwrap();
+        assert!(text.contains("quote_style:\"prefer_double\""));
+        assert!(text.contains("binary_encoding:\"hex\""));
+        assert_eq!(crate::from_str::<Options>(&text).unwrap(), opts);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_partial_document() {
+        let opts: Options = crate::from_str("{indent: '\t', sort_keys: 'natural'}").unwrap();
+        assert_eq!(
+            opts,
+            Options::pretty()
+                .with_indent("\t")
+                .with_sort_keys_mode(SortKeys::Natural)
+        );
+
+        assert!(crate::from_str::<Options>("{indnet: ''}").is_err());
     }
 }
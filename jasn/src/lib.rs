@@ -49,6 +49,9 @@
 //!   timestamp: ts"2024-01-15T12:30:45Z",
 //!   timestamp_offset: ts"2024-01-15T12:30:45-05:00",
 //!   
+//!   /* Durations (ISO 8601) */
+//!   duration: dur"PT1H30M",
+//!   
 //!   /* Lists */
 //!   list: [1, 2, 3, "mixed", true, null],
 //!   nested_list: [[1, 2], [3, 4]],
@@ -111,23 +114,30 @@
 #![warn(missing_docs)]
 
 // Re-export core types
-pub use jasn_core::{Binary, Timestamp, Value};
+pub use jasn_core::{Binary, Timestamp, Value, timestamp};
 
 pub mod parser;
 pub use parser::parse;
 
 pub mod formatter;
-pub use formatter::{format, format_pretty};
+pub use formatter::{format, format_pretty, to_json_string, to_json_string_pretty};
+
+mod value;
+pub use value::ValueExt;
 
 #[cfg(feature = "serde")]
 pub mod de;
 #[cfg(feature = "serde")]
+pub mod duration;
+#[cfg(feature = "serde")]
 pub mod ser;
+#[cfg(feature = "serde")]
+pub mod system_time;
 
 #[cfg(feature = "serde")]
 pub use de::{from_str, from_value};
 #[cfg(feature = "serde")]
-pub use ser::{to_string, to_string_pretty, to_value};
+pub use ser::{to_string, to_string_opts, to_string_pretty, to_value};
 
 /// Complete grammar specification for JASN.
 ///
@@ -12,11 +12,96 @@
 use crate::Value;
 
 mod error;
+mod lint;
+/// Parser options.
+mod options;
 mod parse;
 
 pub use error::{Error, Result};
+pub use lint::{MAX_NESTING_DEPTH, Warning, WarningKind, lint};
+pub use options::ParseOptions;
 
 /// Parse a JASN string into a [`Value`].
 pub fn parse(input: &str) -> Result<Value> {
     parse::parse_impl(input)
 }
+
+/// Parse the first JASN value in `input`, returning it with the byte offset just past it.
+///
+/// Leading whitespace and comments are skipped; anything after the value (including
+/// whitespace) is left unparsed, so callers can loop over several values or report the rest.
+/// Adjacent string literals such as `"a" "b"` are read as separate values.
+///
+/// ```
+/// let (value, end) = jasn::parser::parse_prefix("42 rest").unwrap();
+/// assert_eq!(value, 42);
+/// assert_eq!(end, 2);
+/// ```
+pub fn parse_prefix(input: &str) -> Result<(Value, usize)> {
+    parse::parse_prefix_impl(input, &ParseOptions::default())
+}
+
+/// Parse a JASN string into a [`Value`] with much lower peak memory than [`parse`] for large
+/// documents.
+///
+/// [`parse`] lets pest build the pair tree for the whole document before any [`Value`] exists,
+/// which takes several times the size of the input. This walks lists and maps itself and only
+/// hands scalars and map keys to pest, so peak memory is close to the size of the resulting
+/// [`Value`]. The result is always the same as [`parse`]'s. Invalid input is re-parsed with
+/// [`parse`] to report the error, so failures cost as much as they do there.
+///
+/// ```
+/// let value = jasn::parser::parse_large("[{ id: 1 }, { id: 2 }]").unwrap();
+/// assert_eq!(value, jasn::parse("[{ id: 1 }, { id: 2 }]").unwrap());
+/// ```
+pub fn parse_large(input: &str) -> Result<Value> {
+    parse::parse_large_impl(input, &ParseOptions::default())
+}
+
+/// Like [`parse_large`], accepting the extensions enabled in `opts`.
+pub fn parse_large_with_opts(input: &str, opts: &ParseOptions) -> Result<Value> {
+    parse::parse_large_impl(input, opts)
+}
+
+/// Parse JASN from raw bytes, validating UTF-8 and skipping a leading byte-order mark.
+///
+/// Invalid UTF-8 is reported as [`Error::InvalidUtf8`] with the byte offset of the first
+/// offending byte.
+///
+/// ```
+/// use jasn::parser::{Error, parse_bytes};
+///
+/// assert_eq!(parse_bytes(b"\xEF\xBB\xBF[1]").unwrap(), jasn::Value::from([1]));
+/// assert!(matches!(parse_bytes(b"\"\xFF\""), Err(Error::InvalidUtf8 { offset: 1 })));
+/// ```
+pub fn parse_bytes(input: &[u8]) -> Result<Value> {
+    let text = std::str::from_utf8(input).map_err(|e| Error::InvalidUtf8 {
+        offset: e.valid_up_to(),
+    })?;
+    parse(text.strip_prefix('\u{FEFF}').unwrap_or(text))
+}
+
+/// Render the raw grammar parse tree of a JASN string, for debugging the grammar.
+///
+/// Only the syntax stage runs, so errors such as duplicate keys or bad escapes are not reported.
+///
+/// ```
+/// let tree = jasn::parser::parse_tree("[1]").unwrap();
+/// assert!(tree.contains("list 1:1 0..3 \"[1]\""));
+/// assert!(tree.contains("integer 1:2 1..2 \"1\""));
+/// ```
+pub fn parse_tree(input: &str) -> Result<String> {
+    parse::parse_tree_impl(input)
+}
+
+/// Parse a JASN string into a [`Value`], accepting the extensions enabled in `opts`.
+///
+/// ```
+/// use jasn::parser::{ParseOptions, parse_with_opts};
+///
+/// let opts = ParseOptions::new().with_concat_adjacent_strings(true);
+/// assert_eq!(parse_with_opts(r#""foo" 'bar'"#, &opts).unwrap(), "foobar");
+/// ```
+pub fn parse_with_opts(input: &str, opts: &ParseOptions) -> Result<Value> {
+    parse::parse_impl_with_opts(input, opts)
+}
@@ -1,3 +1,5 @@
+use pest::error::LineColLocation;
+
 use super::parse::PestError;
 
 /// Errors that can occur during parsing.
@@ -36,17 +38,108 @@ pub enum Error {
     #[error("Hex binary must have even number of digits")]
     OddHexDigits,
 
+    /// Non-hex character in a hex binary literal with a custom prefix.
+    #[error("Invalid hex digit: {0:?}")]
+    InvalidHexDigit(char),
+
     /// Unknown binary encoding.
     #[error("Unknown binary encoding: {0}")]
     UnknownBinaryEncoding(String),
 
-    /// Duplicate key in map.
-    #[error("Duplicate key in map: {0}")]
-    DuplicateKey(String),
+    /// Input bytes are not valid UTF-8.
+    #[error("Invalid UTF-8 at byte offset {offset}")]
+    InvalidUtf8 {
+        /// Offset of the first byte that is not part of a valid UTF-8 sequence.
+        offset: usize,
+    },
+
+    /// Input contains only whitespace and comments.
+    #[error("Empty document")]
+    EmptyDocument,
+
+    /// Duplicate key in map, reported at its second occurrence.
+    #[error("Duplicate key in map: {key} at line {line}, column {column}")]
+    DuplicateKey {
+        /// The repeated key.
+        key: String,
+        /// 1-based line of the repeated member.
+        line: usize,
+        /// 1-based column (in characters) of the repeated member.
+        column: usize,
+    },
+
+    /// Lists and maps are nested deeper than [`ParseOptions::max_depth`](super::ParseOptions::max_depth).
+    #[error("Nesting depth exceeds limit of {limit} at line {line}, column {column}")]
+    DepthLimitExceeded {
+        /// The configured limit.
+        limit: usize,
+        /// 1-based line of the first bracket past the limit.
+        line: usize,
+        /// 1-based column (in characters) of the first bracket past the limit.
+        column: usize,
+    },
+
+    /// A `[` or `{` is never closed, either because the input ends or because the next closing
+    /// bracket is of the other kind.
+    #[error("Unclosed '{kind}' opened at line {opened_at_line}, column {opened_at_column}")]
+    UnclosedDelimiter {
+        /// The opening bracket, `[` or `{`.
+        kind: char,
+        /// 1-based line of the opening bracket.
+        opened_at_line: usize,
+        /// 1-based column (in characters) of the opening bracket.
+        opened_at_column: usize,
+    },
 
     /// Invalid timestamp format.
     #[error("Invalid timestamp '{0}': {1}")]
     InvalidTimestamp(String, String),
+
+    /// Invalid ISO 8601 duration.
+    #[error("Invalid duration '{0}': {1}")]
+    InvalidDuration(String, String),
+
+    /// The parse tree did not have the shape the grammar guarantees.
+    ///
+    /// This indicates a bug in the parser rather than in the input.
+    #[error("Internal parser error: {0}")]
+    Internal(String),
+}
+
+impl Error {
+    /// Returns the 1-based line and column (in characters) where a syntax error, duplicate key or
+    /// excessive nesting was detected, or where an unclosed bracket was opened.
+    ///
+    /// The other variants carry no position and return `None`.
+    pub fn line_col(&self) -> Option<(usize, usize)> {
+        match self {
+            Error::PestError(e) => match e.line_col {
+                LineColLocation::Pos(pos) | LineColLocation::Span(pos, _) => Some(pos),
+            },
+            Error::DuplicateKey { line, column, .. }
+            | Error::DepthLimitExceeded { line, column, .. } => Some((*line, *column)),
+            Error::UnclosedDelimiter {
+                opened_at_line,
+                opened_at_column,
+                ..
+            } => Some((*opened_at_line, *opened_at_column)),
+            _ => None,
+        }
+    }
+
+    /// Returns the error description without the source excerpt or position that
+    /// [`Display`](std::fmt::Display) includes for errors with a [`Self::line_col`].
+    pub fn message(&self) -> String {
+        match self {
+            Error::PestError(e) => e.variant.message().into_owned(),
+            Error::DuplicateKey { key, .. } => format!("Duplicate key in map: {key}"),
+            Error::DepthLimitExceeded { limit, .. } => {
+                format!("Nesting depth exceeds limit of {limit}")
+            }
+            Error::UnclosedDelimiter { kind, .. } => format!("Unclosed '{kind}'"),
+            other => other.to_string(),
+        }
+    }
 }
 
 /// Result type for parsing operations.
@@ -0,0 +1,241 @@
+//! Lint checks for suspicious-but-parseable JASN text.
+
+use std::{collections::HashMap, fmt};
+
+use pest::{Parser, iterators::Pair};
+
+use super::{
+    ParseOptions,
+    parse::{JasnParser, Rule, parse_map_key},
+};
+
+/// Nesting depth of lists and maps above which [`WarningKind::DeepNesting`] is reported.
+pub const MAX_NESTING_DEPTH: usize = 32;
+
+/// A lint warning with its 1-based source position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// Line of the offending construct.
+    pub line: usize,
+    /// Column of the offending construct.
+    pub column: usize,
+    /// What was found.
+    pub kind: WarningKind,
+}
+
+/// The kinds of lint warnings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WarningKind {
+    /// A key repeats an earlier key of the same map (which [`parse`](super::parse) rejects).
+    DuplicateKey {
+        /// The repeated key.
+        key: String,
+        /// Line of the first occurrence.
+        first_line: usize,
+    },
+    /// The document contains only whitespace and comments.
+    EmptyDocument,
+    /// Lists and maps are nested deeper than [`MAX_NESTING_DEPTH`].
+    DeepNesting,
+    /// Both single- and double-quoted strings are used in the same document.
+    MixedQuotes,
+}
+
+impl fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarningKind::DuplicateKey { key, first_line } => {
+                write!(
+                    f,
+                    "duplicate key {key:?} (first defined on line {first_line})"
+                )
+            }
+            WarningKind::EmptyDocument => write!(f, "empty document"),
+            WarningKind::DeepNesting => {
+                write!(f, "nesting deeper than {MAX_NESTING_DEPTH} levels")
+            }
+            WarningKind::MixedQuotes => write!(f, "mixed single- and double-quoted strings"),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.kind)
+    }
+}
+
+/// Checks JASN text for valid-but-suspicious constructs.
+///
+/// Syntax errors are not reported here; use [`parse`](super::parse) for those. Text that does
+/// not parse yields no warnings.
+///
+/// ```
+/// use jasn::parser::{WarningKind, lint};
+///
+/// let warnings = lint("{a: 1,\n a: 2}");
+/// assert_eq!(warnings.len(), 1);
+/// assert_eq!(warnings[0].line, 2);
+/// assert!(matches!(warnings[0].kind, WarningKind::DuplicateKey { first_line: 1, .. }));
+/// ```
+pub fn lint(input: &str) -> Vec<Warning> {
+    let mut linter = Linter::default();
+
+    if let Ok(pairs) = JasnParser::parse(Rule::jasn, input) {
+        match pairs.flatten().find(|pair| pair.as_rule() == Rule::value) {
+            Some(value) => linter.walk(value, 0),
+            None => linter.warn_at(1, 1, WarningKind::EmptyDocument),
+        }
+    }
+
+    linter.warnings
+}
+
+#[derive(Default)]
+struct Linter {
+    warnings: Vec<Warning>,
+    /// Quote rule of the first string seen, used to detect mixed quoting.
+    quotes: Option<Rule>,
+    reported_quotes: bool,
+    reported_depth: bool,
+}
+
+impl Linter {
+    fn walk(&mut self, pair: Pair<Rule>, depth: usize) {
+        match pair.as_rule() {
+            Rule::value | Rule::key | Rule::strings => {
+                for inner in pair.into_inner() {
+                    self.walk(inner, depth);
+                }
+            }
+            Rule::string => self.check_quotes(pair),
+            Rule::list => {
+                self.check_depth(&pair, depth + 1);
+                for inner in pair.into_inner() {
+                    self.walk(inner, depth + 1);
+                }
+            }
+            Rule::map => {
+                self.check_depth(&pair, depth + 1);
+                let mut seen = HashMap::new();
+                for member in pair.into_inner() {
+                    for inner in member.into_inner() {
+                        if inner.as_rule() == Rule::key {
+                            self.check_key(&inner, &mut seen);
+                        }
+                        self.walk(inner, depth + 1);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_key(&mut self, key: &Pair<Rule>, seen: &mut HashMap<String, usize>) {
+        let Ok(name) = parse_map_key(key.clone(), &ParseOptions::default()) else {
+            return;
+        };
+        let (line, column) = key.line_col();
+        match seen.get(&name) {
+            Some(&first_line) => self.warn_at(
+                line,
+                column,
+                WarningKind::DuplicateKey {
+                    key: name,
+                    first_line,
+                },
+            ),
+            None => {
+                seen.insert(name, line);
+            }
+        }
+    }
+
+    fn check_quotes(&mut self, string: Pair<Rule>) {
+        let Some(quoted) = string.clone().into_inner().next() else {
+            return;
+        };
+        match self.quotes {
+            None => self.quotes = Some(quoted.as_rule()),
+            Some(rule) if rule != quoted.as_rule() && !self.reported_quotes => {
+                self.reported_quotes = true;
+                self.warn(&string, WarningKind::MixedQuotes);
+            }
+            Some(_) => {}
+        }
+    }
+
+    fn check_depth(&mut self, pair: &Pair<Rule>, depth: usize) {
+        if depth > MAX_NESTING_DEPTH && !self.reported_depth {
+            self.reported_depth = true;
+            self.warn(pair, WarningKind::DeepNesting);
+        }
+    }
+
+    fn warn(&mut self, pair: &Pair<Rule>, kind: WarningKind) {
+        let (line, column) = pair.line_col();
+        self.warn_at(line, column, kind);
+    }
+
+    fn warn_at(&mut self, line: usize, column: usize, kind: WarningKind) {
+        self.warnings.push(Warning { line, column, kind });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("{a: 1, b: [1, 2], c: \"x\"}")]
+    #[case("'single'")]
+    #[case("{a: {a: 1}, b: {a: 2}}")]
+    fn test_lint_clean(#[case] input: &str) {
+        assert_eq!(lint(input), vec![]);
+    }
+
+    #[rstest]
+    #[case("{\n  a: 1,\n  \"a\": 2\n}", 3, 3)]
+    #[case("[{x: 1, y: 2, x: 3}]", 1, 15)]
+    fn test_lint_duplicate_key(#[case] input: &str, #[case] line: usize, #[case] column: usize) {
+        let warnings = lint(input);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].line, warnings[0].column), (line, column));
+        assert!(matches!(warnings[0].kind, WarningKind::DuplicateKey { .. }));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("  \n\t")]
+    #[case("/* nothing */\n/* here */")]
+    fn test_lint_empty_document(#[case] input: &str) {
+        let warnings = lint(input);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, WarningKind::EmptyDocument);
+    }
+
+    #[test]
+    fn test_lint_deep_nesting() {
+        let depth = MAX_NESTING_DEPTH + 2;
+        let input = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        let warnings = lint(&input);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, WarningKind::DeepNesting);
+        assert_eq!(warnings[0].column, MAX_NESTING_DEPTH + 1);
+    }
+
+    #[test]
+    fn test_lint_mixed_quotes() {
+        let warnings = lint("{a: \"x\",\n b: 'y', c: 'z'}");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, WarningKind::MixedQuotes);
+        assert_eq!((warnings[0].line, warnings[0].column), (2, 5));
+        assert_eq!(
+            warnings[0].to_string(),
+            "2:5: mixed single- and double-quoted strings"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use crate::formatter::BinaryEncoding;
+
+/// Options controlling which extensions the JASN parser accepts.
+///
+/// The default accepts exactly the JASN grammar; each option opts into a relaxation or, for
+/// [`reject_raw_control_chars`](Self::reject_raw_control_chars) and
+/// [`max_depth`](Self::max_depth), a stricter check.
+///
+/// There is no option to intern repeated map keys: [`Value::Map`](crate::Value::Map) owns each
+/// key as a `String`, so every parsed key is its own allocation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Concatenate adjacent string literals in value position (`"foo" 'bar'` → `"foobar"`).
+    ///
+    /// The literals may be separated by whitespace and comments. Map keys are never
+    /// concatenated.
+    pub concat_adjacent_strings: bool,
+
+    /// Reject control characters (such as a literal tab) written unescaped inside string
+    /// literals, in both values and quoted keys.
+    ///
+    /// Unlike the other options this makes the parser stricter than the JASN grammar.
+    pub reject_raw_control_chars: bool,
+
+    /// Accept the JavaScript keyword `undefined` in value position and parse it as
+    /// [`Value::Null`](crate::Value::Null).
+    pub treat_undefined_as_null: bool,
+
+    /// Maximum nesting depth of lists and maps, or `None` for no limit.
+    ///
+    /// A root `[]` has depth 1. The limit is checked before the grammar runs, so deeply nested
+    /// untrusted input is rejected without recursing into it.
+    pub max_depth: Option<usize>,
+
+    /// Extra binary literal prefixes and the encoding of their content, such as `base64` for
+    /// `base64"SGk="` or `0x` for `0x"4869"`.
+    ///
+    /// The built-in `b64` and `hex` prefixes are always accepted and cannot be redefined, and
+    /// `ts` is reserved for timestamps. Prefixes may contain only ASCII letters, digits and
+    /// `_`; other entries never match.
+    pub binary_prefixes: BTreeMap<String, BinaryEncoding>,
+}
+
+impl ParseOptions {
+    /// Creates options that accept only standard JASN.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether adjacent string literals are concatenated.
+    pub fn with_concat_adjacent_strings(mut self, enable: bool) -> Self {
+        self.concat_adjacent_strings = enable;
+        self
+    }
+
+    /// Sets whether unescaped control characters in strings are rejected.
+    pub fn with_reject_raw_control_chars(mut self, enable: bool) -> Self {
+        self.reject_raw_control_chars = enable;
+        self
+    }
+
+    /// Sets whether `undefined` is accepted as an alias for `null`.
+    pub fn with_treat_undefined_as_null(mut self, enable: bool) -> Self {
+        self.treat_undefined_as_null = enable;
+        self
+    }
+
+    /// Sets the maximum nesting depth of lists and maps.
+    pub fn with_max_depth(mut self, limit: Option<usize>) -> Self {
+        self.max_depth = limit;
+        self
+    }
+
+    /// Registers an extra binary literal prefix; see [`Self::binary_prefixes`].
+    pub fn with_binary_prefix(
+        mut self,
+        prefix: impl Into<String>,
+        encoding: BinaryEncoding,
+    ) -> Self {
+        self.binary_prefixes.insert(prefix.into(), encoding);
+        self
+    }
+}
@@ -5,13 +5,17 @@
 // Suppress warnings from pest-generated Parser code
 #![allow(missing_docs)]
 
-use std::{collections::BTreeMap, result::Result as StdResult};
+use std::{borrow::Cow, collections::BTreeMap, result::Result as StdResult};
 
-use pest::{Parser, iterators::Pair};
+use pest::{
+    Parser,
+    error::{ErrorVariant, InputLocation},
+    iterators::{Pair, Pairs},
+};
 use pest_derive::Parser;
 
-use super::{Error, Result};
-use crate::{Binary, Timestamp, Value};
+use super::{Error, ParseOptions, Result};
+use crate::{Binary, Timestamp, Value, formatter::BinaryEncoding};
 
 pub(super) type PestError = pest::error::Error<Rule>;
 
@@ -20,32 +24,341 @@ pub(super) type PestError = pest::error::Error<Rule>;
 #[grammar = "parser/grammar.pest"]
 pub(super) struct JasnParser;
 
+/// Renders the raw pest parse tree of `input`, one pair per line, indented by depth.
+///
+/// Each line shows the rule name, the `line:column` and byte range of its span, and its text.
+pub(super) fn parse_tree_impl(input: &str) -> Result<String> {
+    let mut out = String::new();
+    for pair in JasnParser::parse(Rule::jasn, input)? {
+        write_tree(&mut out, pair, 0);
+    }
+    Ok(out)
+}
+
+fn write_tree(out: &mut String, pair: Pair<Rule>, depth: usize) {
+    let span = pair.as_span();
+    let (line, column) = span.start_pos().line_col();
+    out.push_str(&format!(
+        "{:indent$}{:?} {}:{} {}..{} {:?}\n",
+        "",
+        pair.as_rule(),
+        line,
+        column,
+        span.start(),
+        span.end(),
+        span.as_str(),
+        indent = depth * 2,
+    ));
+    for inner in pair.into_inner() {
+        write_tree(out, inner, depth + 1);
+    }
+}
+
 pub(super) fn parse_impl(input: &str) -> Result<Value> {
-    let mut pairs = JasnParser::parse(Rule::jasn, input)?;
-    let pair = pairs.next().unwrap(); // jasn rule
-    let inner = pair.into_inner().next().unwrap(); // value rule
-    parse_value(inner)
+    parse_impl_with_opts(input, &ParseOptions::default())
+}
+
+pub(super) fn parse_impl_with_opts(input: &str, opts: &ParseOptions) -> Result<Value> {
+    check_depth(input, opts)?;
+    let mut pairs = JasnParser::parse(Rule::jasn, input).map_err(|e| syntax_error(input, e))?;
+    let pair = next_pair(&mut pairs, Rule::jasn)?; // jasn rule
+    match pair.into_inner().next() {
+        Some(inner) if inner.as_rule() == Rule::value => parse_value(inner, opts),
+        _ => Err(Error::EmptyDocument), // only whitespace and comments before EOI
+    }
+}
+
+pub(super) fn parse_prefix_impl(input: &str, opts: &ParseOptions) -> Result<(Value, usize)> {
+    // Without concatenation, adjacent string literals are separate values in a stream
+    let rule = if opts.concat_adjacent_strings {
+        Rule::jasn_prefix
+    } else {
+        Rule::jasn_single_prefix
+    };
+    let mut pairs = JasnParser::parse(rule, input)?;
+    let pair = next_pair(&mut pairs, rule)?;
+    match pair.into_inner().next() {
+        Some(inner) if inner.as_rule() == Rule::value => {
+            let end = inner.as_span().end();
+            Ok((parse_value(inner, opts)?, end))
+        }
+        Some(inner) if inner.as_rule() == Rule::string => {
+            let end = inner.as_span().end();
+            Ok((parse_string(inner, opts)?, end))
+        }
+        _ => Err(Error::EmptyDocument),
+    }
+}
+
+/// Rejects input whose lists and maps nest deeper than [`ParseOptions::max_depth`].
+///
+/// This is a plain byte scan that skips strings and comments, so it never recurses. Malformed
+/// input is left for the grammar to report.
+fn check_depth(input: &str, opts: &ParseOptions) -> Result<()> {
+    let Some(limit) = opts.max_depth else {
+        return Ok(());
+    };
+
+    let mut depth = 0;
+    scan_brackets(input, |offset, bracket| {
+        if matches!(bracket, b'[' | b'{') {
+            depth += 1;
+            if depth > limit {
+                let (line, column) = line_col_at(input, offset)?;
+                return Err(Error::DepthLimitExceeded {
+                    limit,
+                    line,
+                    column,
+                });
+            }
+        } else {
+            depth = depth.saturating_sub(1);
+        }
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+/// Turns a pest error into [`Error::UnclosedDelimiter`] when a `[` or `{` opened before the
+/// error position is never closed: the input ends with it still open, or the next closing
+/// bracket (after the error) is of the other kind.
+///
+/// pest reports such errors at the last value it could not continue from, which says nothing
+/// about the missing bracket.
+fn syntax_error(input: &str, error: PestError) -> Error {
+    let error_offset = match error.location {
+        InputLocation::Pos(offset) | InputLocation::Span((offset, _)) => offset,
+    };
+
+    let mut open = Vec::new();
+    let scan = scan_brackets(input, |offset, bracket| match bracket {
+        b'[' | b'{' => {
+            open.push((offset, bracket));
+            Ok(())
+        }
+        // `[` + 2 is `]` and `{` + 2 is `}`
+        _ => match open.pop() {
+            Some((_, opener)) if opener + 2 == bracket => Ok(()),
+            opener => Err((opener, offset)),
+        },
+    });
+
+    let unclosed = match scan {
+        Ok(true) => open.last().copied(),
+        // A closing bracket at the error position is itself the mistake, as in `{a: ]}`
+        Err((opener, closer)) if closer > error_offset => opener,
+        Err(_) | Ok(false) => None, // or an unterminated string or comment
+    };
+    match unclosed {
+        Some((offset, kind)) if offset < error_offset => match line_col_at(input, offset) {
+            Ok((line, column)) => Error::UnclosedDelimiter {
+                kind: char::from(kind),
+                opened_at_line: line,
+                opened_at_column: column,
+            },
+            Err(e) => e,
+        },
+        _ => Error::PestError(error),
+    }
+}
+
+/// Calls `f` with the byte offset of every bracket outside strings and comments, stopping at
+/// the first error.
+///
+/// Returns `false` if a string or comment is never terminated.
+fn scan_brackets<E>(
+    input: &str,
+    mut f: impl FnMut(usize, u8) -> StdResult<(), E>,
+) -> StdResult<bool, E> {
+    let bytes = input.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            bracket @ (b'[' | b'{' | b']' | b'}') => f(i, bracket)?,
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                if i >= bytes.len() {
+                    return Ok(false);
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => match input[i + 2..].find("*/") {
+                Some(end) => i += end + 3,
+                None => return Ok(false),
+            },
+            _ => {}
+        }
+        i += 1;
+    }
+
+    Ok(true)
+}
+
+/// Converts a byte offset into a 1-based line and column (in characters).
+fn line_col_at(input: &str, offset: usize) -> Result<(usize, usize)> {
+    pest::Position::new(input, offset)
+        .map(|pos| pos.line_col())
+        .ok_or_else(|| Error::Internal("bracket offset off a char boundary".into()))
+}
+
+/// Parses `input` without building a pest pair tree for the whole document.
+///
+/// Lists and maps are walked here and only scalars and map keys go through pest, so the pairs
+/// for each one are dropped as soon as its [`Value`] is built. Any failure is reported by
+/// re-running [`parse_impl_with_opts`] on the full input, so errors (and their positions) are identical
+/// to the regular parser's.
+pub(super) fn parse_large_impl(input: &str, opts: &ParseOptions) -> Result<Value> {
+    check_depth(input, opts)?;
+    let mut walker = LargeParser {
+        input,
+        pos: 0,
+        opts,
+    };
+    match walker.document() {
+        Some(value) => Ok(value),
+        None => parse_impl_with_opts(input, opts),
+    }
+}
+
+struct LargeParser<'a> {
+    input: &'a str,
+    pos: usize,
+    opts: &'a ParseOptions,
+}
+
+impl<'a> LargeParser<'a> {
+    fn document(&mut self) -> Option<Value> {
+        let value = self.value()?;
+        self.skip_trivia()?;
+        (self.pos == self.input.len()).then_some(value)
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        self.skip_trivia()?;
+        match self.peek()? {
+            b'[' => self.list(),
+            b'{' => self.map(),
+            _ => {
+                let (value, len) = parse_prefix_impl(self.rest(), self.opts).ok()?;
+                self.pos += len;
+                Some(value)
+            }
+        }
+    }
+
+    fn list(&mut self) -> Option<Value> {
+        self.pos += 1; // [
+        let mut items = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            if self.eat(b']') {
+                return Some(Value::List(items));
+            }
+            items.push(self.value()?);
+            self.skip_trivia()?;
+            if !self.eat(b',') {
+                self.skip_trivia()?;
+                return self.eat(b']').then_some(Value::List(items));
+            }
+        }
+    }
+
+    fn map(&mut self) -> Option<Value> {
+        self.pos += 1; // {
+        let mut map = BTreeMap::new();
+        loop {
+            self.skip_trivia()?;
+            if self.eat(b'}') {
+                return Some(Value::Map(map));
+            }
+            let key = self.key()?;
+            self.skip_trivia()?;
+            if !self.eat(b':') {
+                return None;
+            }
+            let value = self.value()?;
+            if map.insert(key, value).is_some() {
+                return None; // duplicate key
+            }
+            self.skip_trivia()?;
+            if !self.eat(b',') {
+                self.skip_trivia()?;
+                return self.eat(b'}').then_some(Value::Map(map));
+            }
+        }
+    }
+
+    fn key(&mut self) -> Option<String> {
+        let mut pairs = JasnParser::parse(Rule::jasn_key_prefix, self.rest()).ok()?;
+        let key = pairs.next()?.into_inner().next()?;
+        let end = key.as_span().end();
+        let key = parse_map_key(key, self.opts).ok()?;
+        self.pos += end;
+        Some(key)
+    }
+
+    /// Skips whitespace and comments; `None` for an unterminated comment.
+    fn skip_trivia(&mut self) -> Option<()> {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\r' | b'\n') => self.pos += 1,
+                Some(b'/') if self.rest().starts_with("/*") => {
+                    self.pos += self.rest()[2..].find("*/")? + 4;
+                }
+                _ => return Some(()),
+            }
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
 }
 
-fn parse_value(pair: Pair<Rule>) -> Result<Value> {
+fn parse_value(pair: Pair<Rule>, opts: &ParseOptions) -> Result<Value> {
     let rule = if pair.as_rule() == Rule::value {
         // value is a wrapper, get the actual inner rule
-        pair.into_inner().next().unwrap()
+        first_inner(pair)?
     } else {
         pair
     };
 
     match rule.as_rule() {
         Rule::null => Ok(Value::Null),
+        Rule::undefined if opts.treat_undefined_as_null => Ok(Value::Null),
+        Rule::undefined => Err(Error::PestError(PestError::new_from_span(
+            ErrorVariant::CustomError {
+                message: "`undefined` is not valid JASN without \
+                          ParseOptions::treat_undefined_as_null"
+                    .to_string(),
+            },
+            rule.as_span(),
+        ))),
         Rule::boolean => Ok(Value::Bool(rule.as_str() == "true")),
         Rule::integer => parse_int(rule),
         Rule::float => parse_float(rule),
-        Rule::string => parse_string(rule),
-        Rule::binary => parse_binary(rule),
+        Rule::strings => parse_strings(rule, opts),
+        Rule::binary => parse_binary(rule, opts),
         Rule::timestamp => parse_timestamp(rule),
-        Rule::list => parse_list(rule),
-        Rule::map => parse_map(rule),
-        _ => unreachable!("Unexpected rule: {:?}", rule.as_rule()),
+        Rule::duration => parse_duration(rule),
+        Rule::list => parse_list(rule, opts),
+        Rule::map => parse_map(rule, opts),
+        other => Err(unexpected_rule(other)),
     }
 }
 
@@ -94,14 +407,59 @@ fn parse_float(pair: Pair<Rule>) -> Result<Value> {
     Ok(Value::Float(value))
 }
 
-fn parse_string(pair: Pair<Rule>) -> Result<Value> {
+/// Parses one or more adjacent string literals, concatenating them if `opts` allows it.
+fn parse_strings(pair: Pair<Rule>, opts: &ParseOptions) -> Result<Value> {
+    let mut literals = pair.into_inner();
+    let mut result = match parse_string(next_pair(&mut literals, Rule::strings)?, opts)? {
+        Value::String(s) => s,
+        _ => return Err(unexpected_rule(Rule::string)),
+    };
+
+    for literal in literals {
+        if !opts.concat_adjacent_strings {
+            return Err(Error::PestError(PestError::new_from_span(
+                ErrorVariant::CustomError {
+                    message: "adjacent string literals are not allowed without \
+                              ParseOptions::concat_adjacent_strings"
+                        .to_string(),
+                },
+                literal.as_span(),
+            )));
+        }
+        if let Value::String(s) = parse_string(literal, opts)? {
+            result.push_str(&s);
+        }
+    }
+
+    Ok(Value::String(result))
+}
+
+fn parse_string(pair: Pair<Rule>, opts: &ParseOptions) -> Result<Value> {
     // The string rule contains the entire string with quotes due to $
     // We need to get the inner content
-    let mut inner = pair.into_inner();
-    let quoted = inner.next().unwrap(); // double_quoted_string or single_quoted_string
-    let content_pair = quoted.into_inner().next().unwrap(); // The actual content
+    let quoted = first_inner(pair)?; // double_quoted_string or single_quoted_string
+    let content_pair = first_inner(quoted)?; // The actual content
     let content = content_pair.as_str();
 
+    if opts.reject_raw_control_chars
+        && let Some((offset, ch)) = content.char_indices().find(|(_, ch)| ch.is_control())
+    {
+        let span = content_pair.as_span();
+        let start = span.start() + offset;
+        let span = pest::Span::new(span.get_input(), start, start + ch.len_utf8())
+            .ok_or_else(|| Error::Internal("control character span out of range".to_string()))?;
+        return Err(Error::PestError(PestError::new_from_span(
+            ErrorVariant::CustomError {
+                message: format!(
+                    "unescaped control character U+{:04X} in string (escape it, or disable \
+                     ParseOptions::reject_raw_control_chars)",
+                    ch as u32
+                ),
+            },
+            span,
+        )));
+    }
+
     // Process escape sequences
     let mut result = String::with_capacity(content.len());
     let mut chars = content.chars();
@@ -118,6 +476,7 @@ fn parse_string(pair: Pair<Rule>) -> Result<Value> {
                 Some('n') => result.push('\n'),
                 Some('r') => result.push('\r'),
                 Some('t') => result.push('\t'),
+                Some('0') => result.push('\0'),
                 Some('u') => result.push(parse_unicode_escape(&mut chars)?),
                 Some(c) => return Err(Error::InvalidEscapeChar(c)),
                 None => return Err(Error::InvalidEscapeChar('\\')),
@@ -166,35 +525,53 @@ fn parse_unicode_escape(chars: &mut std::str::Chars) -> Result<char> {
     char::from_u32(code).ok_or(Error::InvalidUnicodeCodepoint(code))
 }
 
-fn parse_binary(pair: Pair<Rule>) -> Result<Value> {
+fn parse_binary(pair: Pair<Rule>, opts: &ParseOptions) -> Result<Value> {
     let s = pair.as_str();
-
-    let bytes = match s {
-        s if s.starts_with("b64\"") => {
-            let content = &s[4..s.len() - 1]; // Remove b64" and "
-            parse_binary_b64(content)?
-        }
-        s if s.starts_with("hex\"") => {
-            let content = &s[4..s.len() - 1]; // Remove hex" and "
-            parse_binary_hex(content)?
-        }
-        _ => {
-            let encoding = s.split('"').next().unwrap_or(s);
-            return Err(Error::UnknownBinaryEncoding(encoding.to_string()));
-        }
+    let (prefix, quoted) = s.split_at(s.find('"').unwrap_or(s.len()));
+    let content = quoted
+        .get(1..quoted.len().saturating_sub(1))
+        .unwrap_or_default(); // Remove quotes
+
+    let encoding = match prefix {
+        "b64" => BinaryEncoding::Base64,
+        "hex" => BinaryEncoding::Hex,
+        _ => *opts
+            .binary_prefixes
+            .get(prefix)
+            .ok_or_else(|| Error::UnknownBinaryEncoding(prefix.to_string()))?,
+    };
+    let bytes = match encoding {
+        BinaryEncoding::Base64 => parse_binary_b64(content)?,
+        BinaryEncoding::Hex => parse_binary_hex(content)?,
     };
 
     Ok(Value::Binary(Binary(bytes)))
 }
 
+/// Drops the whitespace that wrapped binary literals may contain.
+fn strip_binary_whitespace(content: &str) -> Cow<'_, str> {
+    if content.contains(|c: char| c.is_ascii_whitespace()) {
+        Cow::Owned(content.split_ascii_whitespace().collect())
+    } else {
+        Cow::Borrowed(content)
+    }
+}
+
 fn parse_binary_b64(content: &str) -> Result<Vec<u8>> {
+    // The standard engine only accepts canonical base64 (required padding, zero trailing
+    // bits), so every accepted literal re-encodes to the same text
     Ok(base64::Engine::decode(
         &base64::engine::general_purpose::STANDARD,
-        content,
+        strip_binary_whitespace(content).as_ref(),
     )?)
 }
 
 fn parse_binary_hex(content: &str) -> Result<Vec<u8>> {
+    let content = strip_binary_whitespace(content);
+    // Custom prefixes share the base64 character set in the grammar
+    if let Some(c) = content.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(Error::InvalidHexDigit(c));
+    }
     if !content.len().is_multiple_of(2) {
         return Err(Error::OddHexDigits);
     }
@@ -219,28 +596,41 @@ fn parse_timestamp(pair: Pair<Rule>) -> Result<Value> {
     Ok(Value::Timestamp(dt))
 }
 
-fn parse_list(pair: Pair<Rule>) -> Result<Value> {
+fn parse_duration(pair: Pair<Rule>) -> Result<Value> {
+    let s = pair.as_str();
+
+    // Extract the content between dur" and "
+    let content = &s[4..s.len() - 1];
+
+    let duration = jasn_core::duration::from_iso8601(content)
+        .map_err(|e| Error::InvalidDuration(content.to_string(), e.to_string()))?;
+
+    Ok(Value::Duration(duration))
+}
+
+fn parse_list(pair: Pair<Rule>, opts: &ParseOptions) -> Result<Value> {
     let values = pair
         .into_inner()
-        .map(parse_value)
+        .map(|item| parse_value(item, opts))
         .collect::<StdResult<Vec<_>, _>>()?;
     Ok(Value::List(values))
 }
 
-fn parse_map(pair: Pair<Rule>) -> Result<Value> {
+fn parse_map(pair: Pair<Rule>, opts: &ParseOptions) -> Result<Value> {
     let mut map = BTreeMap::new();
 
     for member in pair.into_inner() {
+        let (line, column) = member.line_col();
         let mut inner = member.into_inner();
-        let key_pair = inner.next().unwrap();
-        let value_pair = inner.next().unwrap();
+        let key_pair = next_pair(&mut inner, Rule::member)?;
+        let value_pair = next_pair(&mut inner, Rule::member)?;
 
-        let key = parse_map_key(key_pair)?;
-        let value = parse_value(value_pair)?;
+        let key = parse_map_key(key_pair, opts)?;
+        let value = parse_value(value_pair, opts)?;
 
         // Check for duplicate keys
         if map.contains_key(&key) {
-            return Err(Error::DuplicateKey(key));
+            return Err(Error::DuplicateKey { key, line, column });
         }
 
         map.insert(key, value);
@@ -249,27 +639,47 @@ fn parse_map(pair: Pair<Rule>) -> Result<Value> {
     Ok(Value::Map(map))
 }
 
-fn parse_map_key(pair: Pair<Rule>) -> Result<String> {
+pub(super) fn parse_map_key(pair: Pair<Rule>, opts: &ParseOptions) -> Result<String> {
     match pair.as_rule() {
         Rule::key => {
             // key is a wrapper rule, extract the actual string or identifier
-            let actual_key = pair.into_inner().next().unwrap();
-            parse_map_key(actual_key)
+            let actual_key = first_inner(pair)?;
+            parse_map_key(actual_key, opts)
         }
         Rule::string => {
-            if let Value::String(s) = parse_string(pair)? {
-                Ok(s)
-            } else {
-                unreachable!("parse_string should always return Value::String")
+            let rule = pair.as_rule();
+            match parse_string(pair, opts)? {
+                Value::String(s) => Ok(s),
+                _ => Err(unexpected_rule(rule)),
             }
         }
         Rule::identifier => Ok(pair.as_str().to_string()),
-        _ => unreachable!("Unexpected rule for map key: {:?}", pair.as_rule()),
+        other => Err(unexpected_rule(other)),
     }
 }
 
+/// Returns the next pair from `pairs`, or an internal error if the parse tree is shorter than
+/// the grammar guarantees.
+fn next_pair<'i>(pairs: &mut Pairs<'i, Rule>, parent: Rule) -> Result<Pair<'i, Rule>> {
+    pairs
+        .next()
+        .ok_or_else(|| Error::Internal(format!("missing child of {:?}", parent)))
+}
+
+/// Returns the first inner pair of `pair`, or an internal error if it has none.
+fn first_inner(pair: Pair<Rule>) -> Result<Pair<Rule>> {
+    let rule = pair.as_rule();
+    next_pair(&mut pair.into_inner(), rule)
+}
+
+fn unexpected_rule(rule: Rule) -> Error {
+    Error::Internal(format!("unexpected rule: {:?}", rule))
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use rstest::rstest;
 
     use super::*;
@@ -292,10 +702,31 @@ mod tests {
     #[case("0xFF", 255)]
     #[case("0b1010", 10)]
     #[case("0o755", 493)]
+    #[case("0XfF", 255)]
+    #[case("0xaBcD", 0xABCD)]
+    #[case("0B1010", 10)]
+    #[case("0O17", 15)]
+    #[case("0xFF_FF", 0xFFFF)]
+    #[case("0B1010_1010", 0b1010_1010)]
+    #[case("-0XfF", -255)]
+    #[case("+0o1_0", 8)]
     fn test_parse_integer(#[case] input: &str, #[case] expected: i64) {
         assert_eq!(parse_impl(input).unwrap(), Value::Int(expected));
     }
 
+    #[rstest]
+    #[case("0XfF")]
+    #[case("0B1010")]
+    #[case("0O17")]
+    #[case("0xFF_FF")]
+    fn test_prefixed_integer_round_trip(#[case] input: &str) {
+        let value = parse_impl(input).unwrap();
+        // The formatter always writes decimal
+        let formatted = crate::format(&value);
+        assert_eq!(formatted, value.as_int().unwrap().to_string());
+        assert_eq!(parse_impl(&formatted).unwrap(), value);
+    }
+
     #[rstest]
     #[case("1__000", 1000)]
     #[case("1___000", 1000)]
@@ -310,6 +741,12 @@ mod tests {
     #[rstest]
     #[case("2.5", 2.5)]
     #[case("1e10", 1e10)]
+    #[case("1E10", 1e10)]
+    #[case("2E+5", 2e5)]
+    #[case("3.0e-7", 3.0e-7)]
+    #[case("5e0", 5.0)]
+    #[case("-1.5E-3", -1.5e-3)]
+    #[case("+.5e+1", 5.0)]
     fn test_parse_float_numbers(#[case] input: &str, #[case] expected: f64) {
         assert_eq!(parse_impl(input).unwrap(), Value::Float(expected));
     }
@@ -332,6 +769,23 @@ mod tests {
         assert!(matches!(parse_impl("nan").unwrap(), Value::Float(f) if f.is_nan()));
     }
 
+    #[test]
+    fn test_parse_signed_special_floats_in_collections() {
+        let map = parse_impl("{w: inf, x: -inf, y: nan, z: +inf}").unwrap();
+        let map = map.as_map().unwrap();
+        assert_eq!(map["w"], f64::INFINITY);
+        assert_eq!(map["x"], f64::NEG_INFINITY);
+        assert!(map["y"].as_float().unwrap().is_nan());
+        assert_eq!(map["z"], f64::INFINITY);
+
+        let list = parse_impl("[inf, -inf, nan, +inf]").unwrap();
+        let list = list.as_list().unwrap();
+        assert_eq!(list[0], f64::INFINITY);
+        assert_eq!(list[1], f64::NEG_INFINITY);
+        assert!(list[2].as_float().unwrap().is_nan());
+        assert_eq!(list[3], f64::INFINITY);
+    }
+
     #[rstest]
     #[case("\"hello\"", "hello")]
     #[case("'world'", "world")]
@@ -353,6 +807,8 @@ mod tests {
     #[case(r#"'a\'b'"#, "a'b")]
     #[case(r#""a\bb""#, "a\u{0008}b")]
     #[case(r#""a\fb""#, "a\u{000C}b")]
+    #[case(r#""a\0b""#, "a\0b")]
+    #[case(r#"'\0'"#, "\0")]
     // Unicode escapes
     #[case(r#""\u0041""#, "A")]
     #[case(r#""\u03B1""#, "α")]
@@ -407,11 +863,94 @@ mod tests {
     #[case("b64\"SGVsbG8=\"", b"Hello")]
     #[case("hex\"\"", b"")]
     #[case("b64\"\"", b"")]
+    #[case("b64\"SGVs\n  bG8=\n\"", b"Hello")]
+    #[case("hex\" 48 65\r\n6c\t6c6f \"", b"Hello")]
+    #[case("hex\"4\n8\"", b"H")]
     fn test_parse_binary(#[case] input: &str, #[case] expected: &[u8]) {
         let result = parse_impl(input).unwrap();
         assert!(matches!(result, Value::Binary(ref b) if b.0 == expected));
     }
 
+    #[rstest]
+    #[case("SGk=")]
+    #[case("SGVsbG8=")]
+    #[case("SGVsbA==")]
+    #[case("AAEC")]
+    fn test_parse_binary_b64_canonical(#[case] content: &str) {
+        let value = parse_impl(&format!("b64\"{content}\"")).unwrap();
+        assert_eq!(value.as_binary().unwrap().to_string(), content);
+    }
+
+    #[rstest]
+    #[case("SGk")] // missing padding
+    #[case("SGVsbA=")] // partial padding
+    #[case("SGk==")] // excess padding
+    #[case("SGl=")] // nonzero trailing bits
+    #[case("SGVsbB==")] // nonzero trailing bits
+    #[case("SGk=SGk=")] // padding before the end
+    fn test_parse_binary_b64_non_canonical(#[case] content: &str) {
+        let input = format!("b64\"{content}\"");
+        let err = parse_impl(&input).unwrap_err();
+        assert!(matches!(err, Error::Base64DecodeError(_)), "{err:?}");
+    }
+
+    #[rstest]
+    #[case("base64\"SGk=\"", b"Hi")]
+    #[case("0x\"4869\"", b"Hi")]
+    #[case("h\" 48\n69 \"", b"Hi")]
+    #[case("[0x\"\", 1, 0x10]", b"")]
+    fn test_parse_custom_binary_prefix(#[case] input: &str, #[case] expected: &[u8]) {
+        let opts = ParseOptions::new()
+            .with_binary_prefix("base64", BinaryEncoding::Base64)
+            .with_binary_prefix("0x", BinaryEncoding::Hex)
+            .with_binary_prefix("h", BinaryEncoding::Hex);
+        let value = parse_impl_with_opts(input, &opts).unwrap();
+        let binary = match &value {
+            Value::List(items) => {
+                assert_eq!(items[1..], [Value::Int(1), Value::Int(16)]);
+                &items[0]
+            }
+            other => other,
+        };
+        assert_eq!(binary, &Value::Binary(Binary::from(expected)));
+        assert_eq!(parse_large_impl(input, &opts).unwrap(), value);
+
+        // Without the registration the prefix is rejected
+        assert!(parse_impl(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_custom_binary_prefix_errors() {
+        let opts = ParseOptions::new().with_binary_prefix("0x", BinaryEncoding::Hex);
+        assert!(matches!(
+            parse_impl_with_opts("foo\"SGk=\"", &opts),
+            Err(Error::UnknownBinaryEncoding(ref prefix)) if prefix == "foo"
+        ));
+        assert!(matches!(
+            parse_impl_with_opts("0x\"+f\"", &opts),
+            Err(Error::InvalidHexDigit('+'))
+        ));
+        assert!(matches!(
+            parse_impl_with_opts("0x\"abc\"", &opts),
+            Err(Error::OddHexDigits)
+        ));
+
+        // Built-in prefixes cannot be redefined, and invalid built-in literals stay syntax errors
+        let opts = ParseOptions::new().with_binary_prefix("hex", BinaryEncoding::Base64);
+        assert_eq!(
+            parse_impl_with_opts("hex\"4869\"", &opts).unwrap(),
+            Value::Binary(Binary::from(b"Hi"))
+        );
+        assert!(matches!(
+            parse_impl_with_opts("hex\"SGk=\"", &opts),
+            Err(Error::PestError(_))
+        ));
+        assert!(matches!(
+            parse_impl_with_opts("ts\"SGk=\"", &opts),
+            Err(Error::PestError(_))
+        ));
+    }
+
     #[rstest]
     #[case("ts\"2024-01-15T12:30:45Z\"")]
     #[case("ts\"2024-01-15T12:30:45.123Z\"")]
@@ -445,6 +984,31 @@ mod tests {
         assert!(matches!(result, Value::Timestamp(_)));
     }
 
+    #[rstest]
+    #[case("dur\"PT1H30M\"", Duration::from_secs(5400))]
+    #[case("dur\"P1DT2H\"", Duration::from_secs(93_600))]
+    #[case("dur\"P2W\"", Duration::from_secs(14 * 86_400))]
+    #[case("dur\"PT0.5S\"", Duration::from_millis(500))]
+    #[case("dur\"PT0S\"", Duration::ZERO)]
+    fn test_parse_duration(#[case] input: &str, #[case] expected: Duration) {
+        let result = parse_impl(input).unwrap();
+        assert_eq!(result, Value::Duration(expected));
+    }
+
+    #[rstest]
+    #[case(
+        "dur\"P1M\"",
+        "Invalid duration 'P1M': years and months have no fixed length"
+    )]
+    #[case("dur\"1H\"", "Invalid duration '1H': expected an ISO 8601 duration")]
+    #[case("dur\"PT\"", "Invalid duration 'PT': expected an ISO 8601 duration")]
+    #[case("dur\"\"", "Invalid duration '': expected an ISO 8601 duration")]
+    fn test_parse_duration_invalid(#[case] input: &str, #[case] message: &str) {
+        let err = parse_impl(input).unwrap_err();
+        assert!(matches!(err, Error::InvalidDuration(..)), "{err:?}");
+        assert!(err.to_string().starts_with(message), "{err}");
+    }
+
     #[test]
     fn test_parse_list() {
         let result = parse_impl("[1, 2, 3]").unwrap();
@@ -491,13 +1055,58 @@ mod tests {
             duplicate_key
         );
         match result {
-            Err(Error::DuplicateKey(key)) => {
+            Err(Error::DuplicateKey { key, .. }) => {
                 assert_eq!(key, duplicate_key, "Error should mention the duplicate key");
             }
             _ => panic!("Expected DuplicateKey error, got: {:?}", result),
         }
     }
 
+    #[rstest]
+    #[case("[[1]]", 2, None)]
+    #[case("[[[1]]]", 2, Some((1, 3)))]
+    #[case("{a: {b: []}}", 2, Some((1, 9)))]
+    #[case("{a: {b: 1}, c: [2]}", 2, None)]
+    #[case("42", 0, None)]
+    #[case("[]", 0, Some((1, 1)))]
+    #[case(r#"["[[[", '{{{', b64"", /* [[[ */ []]"#, 2, None)]
+    #[case(r#"["\"[[", [[]]]"#, 2, Some((1, 11)))]
+    #[case("[\n  [\n    [\n    ]\n  ]\n]", 2, Some((3, 5)))]
+    fn test_max_depth(
+        #[case] input: &str,
+        #[case] limit: usize,
+        #[case] error_at: Option<(usize, usize)>,
+    ) {
+        let opts = ParseOptions::new().with_max_depth(Some(limit));
+        match (parse_impl_with_opts(input, &opts), error_at) {
+            (Ok(value), None) => {
+                assert_eq!(value, parse_impl(input).unwrap())
+            }
+            (Err(err @ Error::DepthLimitExceeded { .. }), Some(pos)) => {
+                assert_eq!(err.line_col(), Some(pos));
+                assert!(matches!(
+                    parse_large_impl(input, &opts),
+                    Err(Error::DepthLimitExceeded { .. })
+                ));
+            }
+            (result, _) => panic!("unexpected result for {input:?}: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_key_position() {
+        let err = parse_impl("{a: 1,\n a: 2}").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::DuplicateKey { ref key, line: 2, column: 2 } if key == "a"
+        ));
+        assert_eq!(err.line_col(), Some((2, 2)));
+        assert_eq!(
+            err.to_string(),
+            "Duplicate key in map: a at line 2, column 2"
+        );
+    }
+
     #[test]
     fn test_parse_map_allows_different_keys() {
         // These should be allowed - different keys
@@ -512,4 +1121,107 @@ mod tests {
             _ => panic!("Expected Map value"),
         }
     }
+
+    #[rstest]
+    #[case(r#""foo" "bar""#, "foobar")]
+    #[case(r#""foo" 'bar' "baz""#, "foobarbaz")]
+    #[case("\"a\"\n  /* joined */\n  \"b\"", "ab")]
+    #[case(r#""esc\n" "aped""#, "esc\naped")]
+    #[case(r#""single""#, "single")]
+    fn test_parse_adjacent_strings(#[case] input: &str, #[case] expected: &str) {
+        let opts = ParseOptions::new().with_concat_adjacent_strings(true);
+        assert_eq!(parse_impl_with_opts(input, &opts).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_adjacent_strings_in_collections() {
+        let opts = ParseOptions::new().with_concat_adjacent_strings(true);
+        let result = parse_impl_with_opts(r#"{k: "a" "b", l: ["c" 'd', "e"]}"#, &opts).unwrap();
+        assert_eq!(
+            result,
+            Value::from([("k", Value::from("ab")), ("l", Value::from(["cd", "e"]))])
+        );
+
+        // Keys are never concatenated
+        assert!(parse_impl_with_opts(r#"{"a" "b": 1}"#, &opts).is_err());
+    }
+
+    #[test]
+    fn test_parse_null_escape_before_digit() {
+        // `\0` followed by a digit would read like an octal escape, so it is not allowed
+        assert!(parse_impl(r#""\01""#).is_err());
+        assert_eq!(parse_impl(r#""\0\u0031""#).unwrap(), "\u{0}1");
+    }
+
+    #[rstest]
+    #[case("\"a\tb\"")]
+    #[case("'\u{7}'")]
+    #[case("{\"k\te\": 1}")]
+    #[case("[\"ok\", \"x\u{1b}[0m\"]")]
+    fn test_reject_raw_control_chars(#[case] input: &str) {
+        assert!(parse_impl(input).is_ok());
+
+        let opts = ParseOptions::new().with_reject_raw_control_chars(true);
+        let err = parse_impl_with_opts(input, &opts).unwrap_err();
+        assert!(
+            err.to_string().contains("unescaped control character"),
+            "{err}"
+        );
+    }
+
+    #[rstest]
+    #[case("undefined")]
+    #[case("[1, undefined]")]
+    #[case("{a: undefined}")]
+    fn test_undefined_requires_option(#[case] input: &str) {
+        let err = parse_impl(input).unwrap_err();
+        assert!(
+            err.to_string().contains("`undefined` is not valid JASN"),
+            "{err}"
+        );
+
+        let opts = ParseOptions::new().with_treat_undefined_as_null(true);
+        let value = parse_impl_with_opts(input, &opts).unwrap();
+        assert_eq!(value.count_by_type().null, 1);
+    }
+
+    #[test]
+    fn test_undefined_as_null() {
+        let opts = ParseOptions::new().with_treat_undefined_as_null(true);
+        assert_eq!(
+            parse_impl_with_opts("undefined", &opts).unwrap(),
+            Value::Null
+        );
+        assert_eq!(
+            parse_impl_with_opts("{undefined: undefined}", &opts).unwrap(),
+            Value::from([("undefined", Value::Null)])
+        );
+        // Only the exact keyword is accepted
+        assert!(parse_impl_with_opts("undefinedx", &opts).is_err());
+        assert!(parse_impl_with_opts("Undefined", &opts).is_err());
+    }
+
+    #[test]
+    fn test_reject_raw_control_chars_allows_escapes() {
+        let opts = ParseOptions::new().with_reject_raw_control_chars(true);
+        assert_eq!(
+            parse_impl_with_opts(r#""a\tb\u0007\0""#, &opts).unwrap(),
+            "a\tb\u{7}\0"
+        );
+
+        let err = parse_impl_with_opts("[1, \"a\tb\"]", &opts).unwrap_err();
+        assert!(err.to_string().contains("1:7"), "{err}");
+    }
+
+    #[rstest]
+    #[case(r#""foo" "bar""#)]
+    #[case(r#"["foo" 'bar']"#)]
+    fn test_parse_adjacent_strings_rejected_by_default(#[case] input: &str) {
+        let result = parse_impl(input);
+        assert!(
+            matches!(result, Err(Error::PestError(_))),
+            "got {:?}",
+            result
+        );
+    }
 }
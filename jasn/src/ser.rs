@@ -30,13 +30,23 @@ where
 }
 
 /// Serialize a Rust value to a JASN string with custom formatting options.
+///
+/// Fails if [`Options::require_ascii`](formatter::Options::require_ascii) is set and the output
+/// would contain non-ASCII characters.
+///
+/// ```
+/// use jasn::formatter::{Options, QuoteStyle};
+///
+/// let opts = Options::compact().with_quote_style(QuoteStyle::Single);
+/// assert_eq!(jasn::to_string_opts(&"hi", &opts).unwrap(), "'hi'");
+/// ```
 pub fn to_string_opts<T>(value: &T, options: &formatter::Options) -> Result<String>
 where
     T: Serialize,
 {
     // TODO: optimize by directly serializing to string instead of going through Value
     let jasn_value = ser::to_value(value)?;
-    Ok(formatter::format_with_opts(&jasn_value, options))
+    formatter::try_format_with_opts(&jasn_value, options).map_err(|e| Error::Custom(e.to_string()))
 }
 
 /// Serialize a Rust value to a JASN [`Value`].
@@ -0,0 +1,48 @@
+//! Serde helpers for [`std::time::SystemTime`] as RFC3339 strings.
+//!
+//! By default serde represents a `SystemTime` as a `{secs_since_epoch, nanos_since_epoch}` map.
+//! Use this module with `#[serde(with = "jasn::system_time")]` to write it as a readable
+//! UTC timestamp string instead.
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//!
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(with = "jasn::system_time")]
+//!     at: SystemTime,
+//! }
+//!
+//! let event = Event { at: SystemTime::UNIX_EPOCH + Duration::from_secs(1234567890) };
+//! assert_eq!(jasn::to_string(&event).unwrap(), r#"{at:"2009-02-13T23:31:30Z"}"#);
+//! ```
+
+use std::time::SystemTime;
+
+use serde::{Deserialize, Deserializer, Serializer, de::Error as _, ser::Error as _};
+use time::format_description::well_known::Rfc3339;
+
+use crate::Timestamp;
+
+/// Serialize a [`SystemTime`] as an RFC3339 string in UTC.
+pub fn serialize<S>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let formatted = Timestamp::from(*time)
+        .format(&Rfc3339)
+        .map_err(S::Error::custom)?;
+    serializer.serialize_str(&formatted)
+}
+
+/// Deserialize a [`SystemTime`] from an RFC3339 string.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<SystemTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    let timestamp = Timestamp::parse(&s, &Rfc3339).map_err(D::Error::custom)?;
+    Ok(timestamp.into())
+}
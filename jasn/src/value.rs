@@ -0,0 +1,125 @@
+//! Formatting shortcuts and export helpers on [`Value`].
+
+use crate::{
+    Value,
+    formatter::{self, BinaryEncoding},
+};
+
+/// Formatting and export methods for [`Value`].
+///
+/// [`Value`] lives in `jasn-core`, which does not know about the JASN formatter, so these
+/// methods are provided through this trait; bring it into scope to call them.
+///
+/// ```
+/// use jasn::{Value, ValueExt};
+///
+/// let value = Value::from([("a", 1)]);
+/// assert_eq!(value.compact(), "{a:1}");
+/// assert_eq!(value.pretty(), "{\n  a: 1,\n}");
+/// ```
+pub trait ValueExt {
+    /// Formats the value compactly; see [`formatter::format`].
+    fn compact(&self) -> String;
+
+    /// Formats the value with indentation and newlines; see [`formatter::format_pretty`].
+    fn pretty(&self) -> String;
+
+    /// Replaces every binary, timestamp and duration in the tree with a plain string, for
+    /// consumers such as JSON that have no such types.
+    ///
+    /// Binary data is encoded with `binary_encoding` (without the `b64"..."` or `hex"..."`
+    /// wrapper), timestamps become RFC 3339 strings and durations ISO 8601 strings.
+    ///
+    /// ```
+    /// use jasn::{Binary, Value, ValueExt, formatter::BinaryEncoding};
+    ///
+    /// let mut value = Value::from([("data", Value::from(Binary::from(b"hi")))]);
+    /// value.stringify_rich_types(BinaryEncoding::Hex);
+    /// assert_eq!(value, Value::from([("data", "6869")]));
+    /// ```
+    fn stringify_rich_types(&mut self, binary_encoding: BinaryEncoding);
+}
+
+impl ValueExt for Value {
+    fn compact(&self) -> String {
+        formatter::format(self)
+    }
+
+    fn pretty(&self) -> String {
+        formatter::format_pretty(self)
+    }
+
+    fn stringify_rich_types(&mut self, binary_encoding: BinaryEncoding) {
+        match self {
+            Value::Binary(b) => {
+                *self = Value::String(formatter::encode_binary(b, binary_encoding).1);
+            }
+            Value::Timestamp(t) => *self = Value::String(crate::timestamp::to_rfc3339(t)),
+            Value::Duration(d) => *self = Value::String(jasn_core::duration::to_iso8601(d)),
+            Value::List(items) => items
+                .iter_mut()
+                .for_each(|item| item.stringify_rich_types(binary_encoding)),
+            Value::Map(map) => map
+                .values_mut()
+                .for_each(|item| item.stringify_rich_types(binary_encoding)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_compact_and_pretty() {
+        assert_eq!(Value::Int(42).compact(), "42");
+        assert_eq!(Value::Int(42).pretty(), "42");
+
+        let map = Value::from([("a", Value::from([1, 2])), ("b", Value::from("x"))]);
+        assert_eq!(map.compact(), formatter::format(&map));
+        assert!(!map.compact().contains('\n'));
+        assert!(map.pretty().contains('\n'));
+        assert_eq!(crate::parse(&map.pretty()).unwrap(), map);
+    }
+
+    #[rstest]
+    #[case(BinaryEncoding::Base64, "SGk=")]
+    #[case(BinaryEncoding::Hex, "4869")]
+    fn test_stringify_rich_types(#[case] encoding: BinaryEncoding, #[case] encoded: &str) {
+        let mut value = crate::parse(
+            r#"{
+                data: b64"SGk=",
+                events: [
+                    {at: ts"2024-01-15T12:30:45.5-05:00", payload: hex"4869", n: 1},
+                    [ts"1970-01-01T00:00:00Z", dur"PT1H30M"],
+                ],
+                name: "plain",
+                empty: b64"",
+            }"#,
+        )
+        .unwrap();
+        value.stringify_rich_types(encoding);
+
+        let expected = Value::from([
+            ("data", Value::from(encoded)),
+            (
+                "events",
+                Value::from([
+                    Value::from([
+                        ("at", Value::from("2024-01-15T12:30:45.5-05:00")),
+                        ("payload", Value::from(encoded)),
+                        ("n", Value::Int(1)),
+                    ]),
+                    Value::from(["1970-01-01T00:00:00Z", "PT1H30M"]),
+                ]),
+            ),
+            ("name", Value::from("plain")),
+            ("empty", Value::from("")),
+        ]);
+        assert_eq!(value, expected);
+        assert_eq!(value.count_by_type().string, 7);
+    }
+}
@@ -85,6 +85,91 @@ fn test_format_check_invalid() {
         .stderr(predicate::str::contains("not formatted correctly"));
 }
 
+#[test]
+fn test_format_check_exit_codes() {
+    jasn_cmd()
+        .arg("format")
+        .arg("--check-format")
+        .write_stdin("{\n  test: 123,\n}\n")
+        .assert()
+        .code(0)
+        .stdout("")
+        .stderr("");
+
+    jasn_cmd()
+        .arg("format")
+        .arg("--check-format")
+        .write_stdin("{test:123}")
+        .assert()
+        .code(1)
+        .stdout("");
+}
+
+#[test]
+fn test_format_check_quiet() {
+    jasn_cmd()
+        .arg("format")
+        .arg("--check-format")
+        .arg("--quiet")
+        .write_stdin("{test:123}")
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("");
+
+    jasn_cmd()
+        .arg("format")
+        .arg("-q")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--check-format"));
+}
+
+#[test]
+fn test_format_diff() {
+    jasn_cmd()
+        .arg("format")
+        .arg("--diff")
+        .arg("--stdin-filename")
+        .arg("config.jasn")
+        .write_stdin("{b:1,a:2}\n")
+        .assert()
+        .code(1)
+        .stdout(concat!(
+            "--- config.jasn.orig\n",
+            "+++ config.jasn\n",
+            "@@ -1 +1,4 @@\n",
+            "-{b:1,a:2}\n",
+            "+{\n",
+            "+  a: 2,\n",
+            "+  b: 1,\n",
+            "+}\n",
+        ))
+        .stderr("");
+
+    jasn_cmd()
+        .arg("format")
+        .arg("--diff")
+        .write_stdin("{\n  a: 2,\n  b: 1,\n}\n")
+        .assert()
+        .code(0)
+        .stdout("")
+        .stderr("");
+}
+
+#[test]
+fn test_format_diff_with_check_format() {
+    jasn_cmd()
+        .arg("format")
+        .arg("--diff")
+        .arg("--check-format")
+        .write_stdin("{a:1}")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("+  a: 1,"))
+        .stderr(predicate::str::contains("not formatted correctly"));
+}
+
 #[test]
 fn test_check_valid_file() {
     let mut cmd = jasn_cmd();
@@ -105,6 +190,80 @@ fn test_check_invalid_file() {
         .stderr(predicate::str::contains("✗"));
 }
 
+#[test]
+fn test_check_error_caret() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("check")
+        .write_stdin("{\n  a: 1,\n\tb: ]\n}\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid JASN syntax at 3:5: expected value\n  |\n3 | \tb: ]\n  | \t   ^\n",
+        ));
+}
+
+#[test]
+fn test_check_stdin_filename() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("check")
+        .arg("--stdin-filename")
+        .arg("foo.jasn")
+        .write_stdin("{\n  a: 1,\n\tb: ]\n}\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Error: foo.jasn: Invalid JASN syntax at 3:5",
+        ));
+
+    let mut cmd = jasn_cmd();
+    cmd.arg("check")
+        .arg("--lint")
+        .arg("--stdin-filename")
+        .arg("foo.jasn")
+        .write_stdin("{\n  a: 1,\n  a: 2\n}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "warning: foo.jasn:3:3: duplicate key",
+        ));
+
+    let mut cmd = jasn_cmd();
+    cmd.arg("check")
+        .arg("-")
+        .arg("--stdin-filename")
+        .arg("foo.jasn")
+        .write_stdin("[1, 2")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("✗ foo.jasn: Invalid JASN syntax"));
+}
+
+#[test]
+fn test_format_stdin_filename() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--stdin-filename")
+        .arg("foo.jasn")
+        .write_stdin("{a: ")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Failed to parse JASN from foo.jasn",
+        ));
+
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--check-format")
+        .arg("--stdin-filename")
+        .arg("foo.jasn")
+        .write_stdin("{test:123}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "File 'foo.jasn' is not formatted correctly",
+        ));
+}
+
 #[test]
 fn test_check_multiple_files() {
     let mut cmd = jasn_cmd();
@@ -170,6 +329,108 @@ fn test_check_verbose() {
         .stdout(predicate::str::contains("Valid JASN:"));
 }
 
+#[test]
+fn test_check_lint_duplicate_key() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("check")
+        .arg("--lint")
+        .write_stdin("{\n  a: 1,\n  a: 2\n}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "warning: stdin:3:3: duplicate key \"a\" (first defined on line 2)",
+        ));
+}
+
+#[test]
+fn test_check_lint_warning_on_valid_file() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("check")
+        .arg("--lint")
+        .write_stdin(r#"{a: "x", b: 'y'}"#)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("mixed single- and double-quoted"));
+}
+
+#[test]
+fn test_check_lint_clean_file() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("check")
+        .arg("--lint")
+        .write_stdin(r#"{name: "test", tags: ["a", "b"], nested: {name: "inner"}}"#)
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_check_parse_tree() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("check")
+        .arg("--parse-tree")
+        .write_stdin(r#"{a: [1, "x"]}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("jasn 1:1"))
+        .stdout(predicate::str::contains("\n    map 1:1 0..13"))
+        .stdout(predicate::str::contains("identifier 1:2 1..2 \"a\""))
+        .stdout(predicate::str::contains("integer 1:6 5..6 \"1\""))
+        .stdout(predicate::str::contains("string 1:9 8..11 \"\\\"x\\\"\""));
+}
+
+#[test]
+fn test_check_parse_tree_hidden_from_help() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("check")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("parse-tree").not());
+}
+
+#[test]
+fn test_format_header() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--header")
+        .arg("Generated by X\ndo not edit")
+        .write_stdin(r#"{"test": 123}"#)
+        .assert()
+        .success()
+        .stdout("/*\n * Generated by X\n * do not edit\n */\n{\n  test: 123,\n}\n");
+}
+
+#[test]
+fn test_format_raw_output_string() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("-r")
+        .write_stdin(r#""line\n\"quoted\" caf\u00e9""#)
+        .assert()
+        .success()
+        .stdout("line\n\"quoted\" café\n");
+}
+
+#[test]
+fn test_format_raw_output_non_string() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--raw-output")
+        .write_stdin("42")
+        .assert()
+        .success()
+        .stdout("42\n");
+
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--raw-output")
+        .write_stdin(r#"{a: "x"}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#"a: "x""#));
+}
+
 #[test]
 fn test_completions_bash() {
     let mut cmd = jasn_cmd();
@@ -247,6 +508,110 @@ fn test_format_no_trailing_commas() {
         .stdout(predicate::str::contains("123\n}"));
 }
 
+#[test]
+fn test_format_config_file() {
+    let config_file = "/tmp/jasn_test_config.jasn";
+    fs::write(
+        config_file,
+        r#"{ indent: "    ", quote_style: "single", trailing_commas: false }"#,
+    )
+    .unwrap();
+
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--config")
+        .arg(config_file)
+        .write_stdin(r#"{"test": "value"}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("{\n    test: 'value'\n}"));
+
+    // Explicit flags override values from the config file
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--config")
+        .arg(config_file)
+        .arg("--quotes")
+        .arg("double")
+        .arg("--indent")
+        .arg("\t")
+        .write_stdin(r#"{"test": "value"}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("{\n\ttest: \"value\"\n}"));
+
+    fs::remove_file(config_file).ok();
+}
+
+#[test]
+fn test_format_invalid_config_file() {
+    let config_file = "/tmp/jasn_test_invalid_config.jasn";
+    fs::write(config_file, r#"{ quote_style: "backtick" }"#).unwrap();
+
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--config")
+        .arg(config_file)
+        .write_stdin(r#"{"test": 123}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid config file"));
+
+    fs::remove_file(config_file).ok();
+}
+
+#[test]
+fn test_format_indent_count() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--indent-count")
+        .arg("4")
+        .write_stdin(r#"{"test": 123}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\n    test: 123"));
+}
+
+#[test]
+fn test_format_indent_count_tab() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--indent-count")
+        .arg("1")
+        .arg("--indent-char")
+        .arg("tab")
+        .write_stdin(r#"{"test": 123}"#)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\n\ttest: 123"));
+}
+
+#[test]
+fn test_format_invalid_indent() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--indent")
+        .arg(" \t")
+        .write_stdin(r#"{"test": 123}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid indent"));
+}
+
+#[test]
+fn test_format_indent_count_conflicts_with_indent() {
+    let mut cmd = jasn_cmd();
+    cmd.arg("format")
+        .arg("--indent")
+        .arg("\t")
+        .arg("--indent-count")
+        .arg("4")
+        .write_stdin(r#"{"test": 123}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
 #[test]
 fn test_invalid_json_parse_error() {
     let mut cmd = jasn_cmd();
@@ -0,0 +1,114 @@
+//! Formatting already-formatted output must not change it, which `jasn fmt --check` relies on.
+
+use std::{fs, path::Path};
+
+use jasn::{
+    formatter::{
+        BinaryEncoding, Options, QuoteStyle, SortKeys, TimestampPrecision, format_with_opts,
+    },
+    parse,
+};
+
+fn option_sets() -> Vec<(&'static str, Options)> {
+    vec![
+        ("compact", Options::compact()),
+        ("pretty", Options::pretty()),
+        ("pretty_tabs", Options::pretty().with_indent("\t")),
+        ("max_width_40", Options::pretty().with_max_width(40)),
+        ("max_width_120", Options::pretty().with_max_width(120)),
+        (
+            "no_trailing_commas",
+            Options::pretty().with_trailing_commas(false),
+        ),
+        (
+            "single_quotes",
+            Options::pretty()
+                .with_quote_style(QuoteStyle::Single)
+                .with_key_quote_style(QuoteStyle::Single),
+        ),
+        (
+            "prefer_double",
+            Options::compact().with_quote_style(QuoteStyle::PreferDouble),
+        ),
+        ("quoted_keys", Options::pretty().with_unquoted_keys(false)),
+        (
+            "keyword_like_keys",
+            Options::pretty().with_quote_keyword_like_keys(true),
+        ),
+        (
+            "hex",
+            Options::compact().with_binary_encoding(BinaryEncoding::Hex),
+        ),
+        (
+            "wrapped_binary",
+            Options::pretty().with_binary_line_wrap(Some(8)),
+        ),
+        ("leading_plus", Options::compact().with_leading_plus(true)),
+        (
+            "sort_natural",
+            Options::pretty().with_sort_keys_mode(SortKeys::Natural),
+        ),
+        (
+            "sort_case_insensitive",
+            Options::compact().with_sort_keys_mode(SortKeys::CaseInsensitive),
+        ),
+        (
+            "escape_unicode",
+            Options::compact().with_escape_unicode(true),
+        ),
+        ("offset_timestamps", Options::pretty().with_use_zulu(false)),
+        (
+            "seconds",
+            Options::pretty().with_timestamp_precision(TimestampPrecision::Seconds),
+        ),
+        (
+            "nanoseconds",
+            Options::compact().with_timestamp_precision(TimestampPrecision::Nanoseconds),
+        ),
+    ]
+}
+
+/// Asserts that `format(parse(format(parse(input))))` equals `format(parse(input))`.
+fn assert_idempotent(name: &str, input: &str) {
+    let value = parse(input).unwrap_or_else(|e| panic!("{name}: {e}"));
+    for (label, opts) in option_sets() {
+        let once = format_with_opts(&value, &opts);
+        let reparsed =
+            parse(&once).unwrap_or_else(|e| panic!("{name} [{label}] does not reparse: {e}"));
+        let twice = format_with_opts(&reparsed, &opts);
+        assert_eq!(twice, once, "{name} [{label}] is not idempotent");
+    }
+}
+
+#[test]
+fn test_valid_examples_format_idempotently() {
+    let mut examples: Vec<_> = fs::read_dir(Path::new("examples/valid"))
+        .expect("Failed to read valid examples directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jasn"))
+        .collect();
+    examples.sort();
+    assert!(!examples.is_empty(), "No valid examples found");
+
+    for example in examples {
+        let content = fs::read_to_string(&example).unwrap();
+        assert_idempotent(&example.display().to_string(), &content);
+    }
+}
+
+#[test]
+fn test_edge_values_format_idempotently() {
+    for input in [
+        "[]",
+        "{}",
+        "[[], {}, [[]], {a: {}}]",
+        "{'': 1, ' ': 2, 'a b': 3, NaN: 4, null: 5, Item10: 6, item2: 7}",
+        "[1e300, -1e-300, 0.1, -0.0, 1.0, 123456789012345678.0, inf, -inf, nan]",
+        "[-9223372036854775808, 9223372036854775807, 0]",
+        r#"["", "'", "\"", "'\"", "\\/\n\t\u0001", " ", "é😀"]"#,
+        r#"[b64"", hex"00ff", b64"AAECAwQFBgcICQoLDA0ODxAREhM="]"#,
+        r#"[ts"2024-01-15T12:30:45.123456789+05:30", ts"1970-01-01T00:00:00Z"]"#,
+    ] {
+        assert_idempotent(input, input);
+    }
+}
@@ -28,11 +28,13 @@ fn test_all_invalid_examples() {
         let content = fs::read_to_string(&example)
             .unwrap_or_else(|e| panic!("Failed to read {:?}: {}", example, e));
 
-        assert!(
-            parse(&content).is_err(),
+        let err = parse(&content).expect_err(&format!(
             "Invalid example {:?} should have failed to parse but succeeded",
             example
-        );
+        ));
+        // parse_large reports errors through the regular parser
+        let large_err = jasn::parser::parse_large(&content).unwrap_err();
+        assert_eq!(large_err.to_string(), err.to_string(), "{:?}", example);
     }
 }
 
@@ -72,3 +74,122 @@ fn test_various_parse_errors(#[case] input: &str) {
 fn test_integer_overflow(#[case] input: &str) {
     assert!(parse(input).is_err());
 }
+
+#[rstest]
+#[case("{a: 1", '{', 1, 1)]
+#[case("[1, 2", '[', 1, 1)]
+#[case("{a: 1, /* done */\n", '{', 1, 1)]
+#[case("{a: {b: 1}", '{', 1, 1)]
+#[case("[\n  {a: 1},\n  {b: [2]\n]", '{', 3, 3)]
+#[case("{\n  bad: [1, 2, 3\n}", '[', 2, 8)]
+#[case("{s: \"}\", t: '[', /* { */ n: [1", '[', 1, 29)]
+fn test_unclosed_delimiter(
+    #[case] input: &str,
+    #[case] kind: char,
+    #[case] line: usize,
+    #[case] column: usize,
+) {
+    use jasn::parser::Error;
+
+    let err = parse(input).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::UnclosedDelimiter { kind: k, opened_at_line: l, opened_at_column: c }
+                if (k, l, c) == (kind, line, column)
+        ),
+        "{err:?}"
+    );
+    assert_eq!(
+        err.to_string(),
+        format!("Unclosed '{kind}' opened at line {line}, column {column}")
+    );
+    assert_eq!(err.line_col(), Some((line, column)));
+}
+
+#[rstest]
+// Balanced brackets: the syntax error is unrelated
+#[case("{a: 1 b: 2}")]
+// Stray closing bracket
+#[case("[1, 2] ]")]
+// Closing bracket where a value is expected
+#[case("{\n  a: 1,\n  b: ]\n}")]
+// Unterminated string
+#[case("{a: \"x")]
+fn test_unclosed_delimiter_not_reported(#[case] input: &str) {
+    let err = parse(input).unwrap_err();
+    assert!(matches!(err, jasn::parser::Error::PestError(_)), "{err:?}");
+}
+
+#[test]
+fn test_structured_error_variants() {
+    use jasn::parser::Error;
+
+    assert!(matches!(parse(r#"hex"ABC""#), Err(Error::OddHexDigits)));
+    assert!(matches!(
+        parse("9223372036854775808"),
+        Err(Error::ParseIntError(_))
+    ));
+    assert!(matches!(
+        parse(r#"b64"SGVsbG8""#),
+        Err(Error::Base64DecodeError(_))
+    ));
+    assert!(matches!(
+        parse(r#""\ud83d""#),
+        Err(Error::InvalidUnicodeCodepoint(0xD83D))
+    ));
+    assert!(matches!(
+        parse(r#"{a: 1, a: 2}"#),
+        Err(Error::DuplicateKey { ref key, line: 1, column: 8 }) if key == "a"
+    ));
+    assert!(matches!(
+        parse(r#"dur"P1Y""#),
+        Err(Error::InvalidDuration(ref text, _)) if text == "P1Y"
+    ));
+    assert!(matches!(parse("[1, 2 3]"), Err(Error::PestError(_))));
+}
+
+#[test]
+fn test_error_line_col() {
+    let err = parse("{\n  a: 1,\n  b: ]\n}").unwrap_err();
+    assert_eq!(err.line_col(), Some((3, 6)));
+    assert_eq!(err.message(), "expected value");
+
+    let err = parse(r#"hex"ABC""#).unwrap_err();
+    assert_eq!(err.line_col(), None);
+    assert_eq!(err.message(), err.to_string());
+}
+
+#[rstest]
+#[case("")]
+#[case("  ")]
+#[case("\n\t\r\n")]
+#[case("/* just a comment */")]
+#[case("/* one */\n/* two */\n")]
+fn test_empty_document(#[case] input: &str) {
+    use jasn::parser::Error;
+
+    assert!(matches!(parse(input), Err(Error::EmptyDocument)));
+}
+
+#[test]
+fn test_line_comment_is_not_empty_document() {
+    use jasn::parser::Error;
+
+    // JASN only has block comments, so this is a syntax error rather than an empty document
+    assert!(matches!(
+        parse("// just a comment"),
+        Err(Error::PestError(_))
+    ));
+}
+
+#[rstest]
+#[case(b"\"\xFF\"", 1)]
+#[case(b"[1, \"caf\xC3\"]", 8)]
+#[case(b"\xEF\xBB\xBF\xC0", 3)]
+fn test_parse_bytes_invalid_utf8(#[case] input: &[u8], #[case] expected: usize) {
+    assert!(matches!(
+        jasn::parser::parse_bytes(input),
+        Err(jasn::parser::Error::InvalidUtf8 { offset }) if offset == expected
+    ));
+}
@@ -0,0 +1,49 @@
+use jasn::{parse, to_json_string, to_json_string_pretty};
+use serde_json::json;
+
+const DOCUMENT: &str = r#"{
+  name: 'café "quoted"',
+  data: b64"SGVsbG8=",
+  raw: hex"00ff",
+  created: ts"2024-01-15T12:30:45.5Z",
+  ttl: dur"P1DT30M",
+  ratio: 0.25,
+  whole: 2.0,
+  count: -7,
+  "key with spaces": [true, null, [], {}],
+  path: "a/b\tc\u0001",
+}"#;
+
+#[test]
+fn test_json_output_parses_with_serde_json() {
+    let value = parse(DOCUMENT).unwrap();
+    let expected = json!({
+        "name": "café \"quoted\"",
+        "data": "SGVsbG8=",
+        "raw": "AP8=",
+        "created": "2024-01-15T12:30:45.5Z",
+        "ttl": "P1DT30M",
+        "ratio": 0.25,
+        "whole": 2.0,
+        "count": -7,
+        "key with spaces": [true, null, [], {}],
+        "path": "a/b\tc\u{1}",
+    });
+
+    for text in [
+        to_json_string(&value).unwrap(),
+        to_json_string_pretty(&value).unwrap(),
+    ] {
+        let parsed: serde_json::Value =
+            serde_json::from_str(&text).unwrap_or_else(|e| panic!("invalid JSON ({e}):\n{text}"));
+        assert_eq!(parsed, expected);
+    }
+}
+
+#[test]
+fn test_json_output_is_valid_jasn() {
+    let value = parse(DOCUMENT).unwrap();
+    let reparsed = parse(&to_json_string(&value).unwrap()).unwrap();
+    assert_eq!(reparsed.get("count"), Some(&jasn::Value::Int(-7)));
+    assert_eq!(reparsed.get("data"), Some(&jasn::Value::from("SGVsbG8=")));
+}
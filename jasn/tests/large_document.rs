@@ -0,0 +1,66 @@
+//! Peak memory of `parse_large` versus `parse`. This lives in its own test binary (with a
+//! single test) because the counting allocator sees every thread's allocations.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use jasn::{Value, parse, parser::parse_large};
+
+struct PeakAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAlloc = PeakAlloc;
+
+/// Returns the result of `f` and the peak heap growth while it ran.
+fn peak_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let result = f();
+    (result, PEAK.load(Ordering::Relaxed) - base)
+}
+
+#[test]
+fn test_parse_large_peak_memory() {
+    let mut input = String::from("/* synthetic */ { records: [\n");
+    for i in 0..20_000 {
+        input.push_str(&format!(
+            "  {{ id: {i}, name: \"user {i}\", score: {i}.5, tags: [\"a\", \"b\"], ok: true }},\n"
+        ));
+    }
+    input.push_str("] }\n");
+
+    let (value, parse_peak) = peak_during(|| parse(&input).unwrap());
+    let (large, large_peak) = peak_during(|| parse_large(&input).unwrap());
+    assert_eq!(large, value);
+
+    let records = value.as_map().unwrap()["records"].as_list().unwrap();
+    assert_eq!(records.len(), 20_000);
+    assert_eq!(records[7].as_map().unwrap()["name"], "user 7");
+    drop::<Value>(large);
+
+    assert!(
+        large_peak * 2 < parse_peak,
+        "parse_large peak {large_peak} should be well under parse peak {parse_peak}"
+    );
+}
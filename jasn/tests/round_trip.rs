@@ -45,31 +45,13 @@ fn test_round_trip_valid_examples() {
 
 // Helper to compare values, treating NaN as equal to NaN
 fn assert_values_equal(left: &jasn::Value, right: &jasn::Value, context: &str) {
-    use jasn::Value;
-
-    match (left, right) {
-        (Value::Float(l), Value::Float(r)) if l.is_nan() && r.is_nan() => {
-            // Both NaN, consider them equal
-        }
-        (Value::List(l), Value::List(r)) => {
-            assert_eq!(l.len(), r.len(), "List length mismatch in {}", context);
-            for (i, (lv, rv)) in l.iter().zip(r.iter()).enumerate() {
-                assert_values_equal(lv, rv, &format!("{}[{}]", context, i));
-            }
-        }
-        (Value::Map(l), Value::Map(r)) => {
-            assert_eq!(l.len(), r.len(), "Map length mismatch in {}", context);
-            for (key, lv) in l.iter() {
-                let rv = r
-                    .get(key)
-                    .unwrap_or_else(|| panic!("Missing key '{}' in {}", key, context));
-                assert_values_equal(lv, rv, &format!("{}.{}", context, key));
-            }
-        }
-        _ => {
-            assert_eq!(left, right, "Value mismatch in {}", context);
-        }
-    }
+    assert!(
+        left.structural_eq(right),
+        "Value mismatch in {}: {:?} != {:?}",
+        context,
+        left,
+        right
+    );
 }
 
 #[test]
@@ -140,3 +122,49 @@ fn test_string_escaping() {
         assert_eq!(formatted, expected);
     }
 }
+
+#[test]
+fn test_escaped_keys() {
+    let value = parse(r#"{"a\tb": 1, "a\"b": 2, "line1\nline2": 3}"#).unwrap();
+    let map = value.as_map().unwrap();
+    assert_eq!(map["a\tb"], 1);
+    assert_eq!(map["a\"b"], 2);
+    assert_eq!(map["line1\nline2"], 3);
+
+    // Such keys can't be unquoted, so the formatter re-quotes and re-escapes them
+    let formatted = format(&value);
+    assert_eq!(formatted, r#"{"a\tb":1,"a\"b":2,"line1\nline2":3}"#);
+    assert_eq!(parse(&formatted).unwrap(), value);
+
+    let pretty = format_pretty(&value);
+    assert!(pretty.contains(r#"  "a\tb": 1,"#));
+    assert!(pretty.contains(r#"  "a\"b": 2,"#));
+    assert_eq!(parse(&pretty).unwrap(), value);
+}
+
+#[test]
+fn test_wrapped_binary_round_trip() {
+    use jasn::{
+        Binary, Value,
+        formatter::{BinaryEncoding, Options, format_with_opts},
+    };
+
+    let data = Binary((0..=255u8).cycle().take(1000).collect());
+    let value = Value::from([
+        ("blob", Value::Binary(data.clone())),
+        ("nested", Value::from([Value::Binary(data)])),
+    ]);
+
+    for encoding in [BinaryEncoding::Base64, BinaryEncoding::Hex] {
+        let opts = Options::pretty()
+            .with_binary_encoding(encoding)
+            .with_binary_line_wrap(Some(64));
+        let formatted = format_with_opts(&value, &opts);
+
+        for line in formatted.lines() {
+            assert!(line.trim_start().len() <= 64, "line too long: {line:?}");
+        }
+        assert!(formatted.lines().count() > 20);
+        assert_eq!(parse(&formatted).unwrap(), value, "{encoding:?}");
+    }
+}
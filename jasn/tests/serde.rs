@@ -140,6 +140,155 @@ fn test_deserialize_primitives() {
     assert_eq!(data.string_field, "hello");
 }
 
+#[test]
+fn test_deserialize_128_bit_integers() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Data {
+        signed: i128,
+        unsigned: u128,
+    }
+
+    let data: Data = jasn::from_str("{ signed: -7, unsigned: 7 }").unwrap();
+    assert_eq!(
+        data,
+        Data {
+            signed: -7,
+            unsigned: 7
+        }
+    );
+
+    let jasn = format!("{{ signed: {}, unsigned: {} }}", i64::MIN, i64::MAX);
+    let data: Data = jasn::from_str(&jasn).unwrap();
+    assert_eq!(data.signed, i64::MIN as i128);
+    assert_eq!(data.unsigned, i64::MAX as u128);
+
+    let err = jasn::from_str::<Data>("{ signed: 0, unsigned: -1 }").unwrap_err();
+    assert!(err.to_string().contains("-1 is out of range for u128"));
+
+    let err = jasn::from_str::<Data>(r#"{ signed: "0", unsigned: 0 }"#).unwrap_err();
+    assert!(err.to_string().contains("expected i128"));
+}
+
+#[test]
+fn test_deserialize_f32_range() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Data {
+        value: f32,
+    }
+
+    let data: Data = jasn::from_str("{ value: 3.4e38 }").unwrap();
+    assert_eq!(data.value, 3.4e38);
+    let data: Data = jasn::from_str("{ value: -inf }").unwrap();
+    assert_eq!(data.value, f32::NEG_INFINITY);
+    let data: Data = jasn::from_str("{ value: nan }").unwrap();
+    assert!(data.value.is_nan());
+    let data: Data = jasn::from_str("{ value: 1e-50 }").unwrap();
+    assert_eq!(data.value, 0.0);
+
+    let err = jasn::from_str::<Data>("{ value: 1e40 }").unwrap_err();
+    assert!(err.to_string().contains("is out of range for f32"));
+    let err = jasn::from_str::<Data>("{ value: -1e40 }").unwrap_err();
+    assert!(err.to_string().contains("is out of range for f32"));
+}
+
+#[test]
+fn test_deserialize_missing_fields() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Data {
+        name: String,
+        #[serde(default)]
+        count: i64,
+        #[serde(default = "default_tags")]
+        tags: Vec<String>,
+        nickname: Option<String>,
+    }
+
+    fn default_tags() -> Vec<String> {
+        vec!["none".to_string()]
+    }
+
+    let data: Data = jasn::from_str(r#"{ name: "x" }"#).unwrap();
+    assert_eq!(
+        data,
+        Data {
+            name: "x".to_string(),
+            count: 0,
+            tags: vec!["none".to_string()],
+            nickname: None,
+        }
+    );
+
+    let err = jasn::from_str::<Data>("{ count: 3 }").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Deserialization error: missing field `name`"
+    );
+    assert!(matches!(
+        jasn::from_value::<Data>(&jasn::Value::Map(Default::default())),
+        Err(jasn::de::Error::DeserializationError(ref e)) if e.to_string() == "missing field `name`"
+    ));
+}
+
+#[test]
+fn test_list_of_pairs() {
+    let pairs: Vec<(String, i64)> = jasn::from_str(r#"[["b", 2], ["a", 1], ["b", 3]]"#).unwrap();
+    assert_eq!(
+        pairs,
+        vec![
+            ("b".to_string(), 2),
+            ("a".to_string(), 1),
+            ("b".to_string(), 3)
+        ]
+    );
+    assert_eq!(
+        jasn::to_string(&pairs).unwrap(),
+        r#"[["b",2],["a",1],["b",3]]"#
+    );
+
+    let pairs: Vec<(String, Vec<bool>)> = jasn::from_str(r#"[["x", [true]], ["y", []]]"#).unwrap();
+    assert_eq!(
+        pairs,
+        vec![("x".to_string(), vec![true]), ("y".to_string(), vec![])]
+    );
+
+    assert!(jasn::from_str::<Vec<(String, i64)>>(r#"[["a", 1, 2]]"#).is_err());
+    assert!(jasn::from_str::<Vec<(String, i64)>>(r#"[["a"]]"#).is_err());
+}
+
+#[test]
+fn test_integer_keyed_maps() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let map: HashMap<u32, String> = jasn::from_str(r#"{ "1": "one", "20": "twenty" }"#).unwrap();
+    assert_eq!(
+        map,
+        HashMap::from([(1, "one".to_string()), (20, "twenty".to_string())])
+    );
+
+    let map = BTreeMap::from([(-5i64, true), (0, false), (i64::MAX, true)]);
+    let text = jasn::to_string(&map).unwrap();
+    assert_eq!(jasn::from_str::<BTreeMap<i64, bool>>(&text).unwrap(), map);
+
+    let map = HashMap::from([(7u32, vec![1, 2]), (42, vec![])]);
+    let text = jasn::to_string_pretty(&map).unwrap();
+    assert_eq!(
+        jasn::from_str::<HashMap<u32, Vec<i32>>>(&text).unwrap(),
+        map
+    );
+
+    let err = jasn::from_str::<HashMap<u32, i32>>(r#"{ "1": 1, abc: 2 }"#).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains(r#"map key "abc" is not a valid u32"#)
+    );
+
+    let err = jasn::from_str::<BTreeMap<u8, i32>>(r#"{ "256": 1 }"#).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains(r#"map key "256" is not a valid u8"#)
+    );
+}
+
 #[test]
 fn test_deserialize_list() {
     #[derive(Deserialize, Debug, PartialEq)]
@@ -152,6 +301,120 @@ fn test_deserialize_list() {
     assert_eq!(data.items, vec![1, 2, 3]);
 }
 
+#[test]
+fn test_deserialize_fixed_size_byte_array() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Record {
+        id: [u8; 16],
+        tag: Vec<u8>,
+    }
+
+    let jasn = r#"{ id: b64"AAECAwQFBgcICQoLDA0ODw==", tag: hex"cafe" }"#;
+    let record: Record = jasn::from_str(jasn).unwrap();
+    assert_eq!(record.id, core::array::from_fn(|i| i as u8));
+    assert_eq!(record.tag, vec![0xca, 0xfe]);
+
+    // Lists of integers still work
+    let id: [u8; 2] = jasn::from_str("[1, 2]").unwrap();
+    assert_eq!(id, [1, 2]);
+
+    // The length must match exactly
+    let err = jasn::from_str::<[u8; 16]>(r#"hex"0001""#).unwrap_err();
+    assert!(err.to_string().contains("invalid length 2"), "{err}");
+    let err = jasn::from_str::<[u8; 2]>(r#"hex"000102""#).unwrap_err();
+    assert!(err.to_string().contains("invalid length 3"), "{err}");
+}
+
+#[test]
+fn test_deserialize_borrowed_bytes() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Packet<'a> {
+        #[serde(with = "serde_bytes")]
+        payload: &'a [u8],
+    }
+
+    let value = jasn::parse(r#"{ payload: hex"deadbeef" }"#).unwrap();
+    let packet: Packet = jasn::from_value(&value).unwrap();
+    assert_eq!(packet.payload, [0xde, 0xad, 0xbe, 0xef]);
+
+    // The slice points into the value's own buffer
+    let binary = value.as_map().unwrap()["payload"].as_binary().unwrap();
+    assert!(std::ptr::eq(packet.payload, binary.0.as_slice()));
+}
+
+#[test]
+fn test_deserialize_borrowed_str() {
+    use std::borrow::Cow;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Entry<'a> {
+        #[serde(borrow)]
+        name: Cow<'a, str>,
+        id: &'a str,
+    }
+
+    let value = jasn::parse(r#"{ name: "caf\u00e9", id: "x1" }"#).unwrap();
+    let entry: Entry = jasn::from_value(&value).unwrap();
+    assert!(
+        matches!(entry.name, Cow::Borrowed("café")),
+        "{:?}",
+        entry.name
+    );
+    assert_eq!(entry.id, "x1");
+
+    // The string points into the value's own buffer
+    let name = value.as_map().unwrap()["name"].as_string().unwrap();
+    assert!(std::ptr::eq(entry.name.as_ref(), name));
+
+    // Without `#[serde(borrow)]`, serde always copies into a `Cow::Owned`
+    #[derive(Deserialize)]
+    struct Owned<'a> {
+        name: Cow<'a, str>,
+    }
+    let owned: Owned = jasn::from_value(&value).unwrap();
+    assert!(matches!(owned.name, Cow::Owned(_)));
+}
+
+#[test]
+fn test_to_string_keeps_unicode_raw() {
+    assert_eq!(jasn::to_string(&"你好 🌍").unwrap(), "\"你好 🌍\"");
+    assert_eq!(jasn::to_string_pretty(&"你好 🌍").unwrap(), "\"你好 🌍\"");
+}
+
+#[test]
+fn test_newtype_binary_and_timestamp() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Hash(#[serde(with = "serde_bytes")] Vec<u8>);
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Key([u8; 4]);
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct When(#[serde(with = "time::serde::rfc3339")] time::OffsetDateTime);
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Record {
+        hash: Hash,
+        key: Key,
+        when: When,
+    }
+
+    // Native binary and timestamp literals
+    let jasn = r#"{ hash: hex"cafe", key: b64"AQIDBA==", when: ts"2024-01-15T12:30:45Z" }"#;
+    let record: Record = jasn::from_str(jasn).unwrap();
+    assert_eq!(record.hash, Hash(vec![0xca, 0xfe]));
+    assert_eq!(record.key, Key([1, 2, 3, 4]));
+    assert_eq!(
+        record.when,
+        When(time::macros::datetime!(2024-01-15 12:30:45 UTC))
+    );
+
+    let text = jasn::to_string(&record).unwrap();
+    assert_eq!(jasn::from_str::<Record>(&text).unwrap(), record);
+    let value = jasn::to_value(&record).unwrap();
+    assert_eq!(jasn::from_value::<Record>(&value).unwrap(), record);
+}
+
 #[test]
 fn test_deserialize_nested() {
     #[derive(Deserialize, Debug, PartialEq)]
@@ -407,3 +670,175 @@ fn test_custom_format_options() {
     let jasn = jasn::ser::to_string_opts(&data, &opts).unwrap();
     assert!(jasn.contains("test"));
 }
+
+#[test]
+fn test_to_string_opts_reexport() {
+    #[derive(Serialize)]
+    struct Data {
+        name: String,
+    }
+
+    let data = Data {
+        name: "test".to_string(),
+    };
+
+    let opts =
+        jasn::formatter::Options::compact().with_quote_style(jasn::formatter::QuoteStyle::Single);
+    let jasn = jasn::to_string_opts(&data, &opts).unwrap();
+    assert_eq!(jasn, "{name:'test'}");
+}
+
+#[test]
+fn test_duration_roundtrip() {
+    use std::time::Duration;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Config {
+        #[serde(with = "jasn::duration")]
+        timeout: Duration,
+    }
+
+    let original = Config {
+        timeout: Duration::new(90, 250_000_000),
+    };
+    let jasn = jasn::to_string(&original).unwrap();
+    assert_eq!(jasn, "{timeout:90.25}");
+    let parsed: Config = jasn::from_str(&jasn).unwrap();
+    assert_eq!(parsed, original);
+
+    // Integers are accepted as whole seconds
+    let parsed: Config = jasn::from_str("{timeout: 5}").unwrap();
+    assert_eq!(parsed.timeout, Duration::from_secs(5));
+
+    // Negative durations are rejected
+    assert!(jasn::from_str::<Config>("{timeout: -1.0}").is_err());
+}
+
+#[test]
+fn test_system_time_roundtrip() {
+    use std::time::{Duration, SystemTime};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Event {
+        #[serde(with = "jasn::system_time")]
+        at: SystemTime,
+    }
+
+    let original = Event {
+        at: SystemTime::UNIX_EPOCH + Duration::new(1234567890, 500_000_000),
+    };
+    let jasn = jasn::to_string(&original).unwrap();
+    assert_eq!(jasn, r#"{at:"2009-02-13T23:31:30.5Z"}"#);
+    let parsed: Event = jasn::from_str(&jasn).unwrap();
+    assert_eq!(parsed, original);
+
+    // Offsets are normalized to the same instant
+    let parsed: Event = jasn::from_str(r#"{at: "2009-02-13T18:31:30.5-05:00"}"#).unwrap();
+    assert_eq!(parsed, original);
+
+    assert!(jasn::from_str::<Event>(r#"{at: "yesterday"}"#).is_err());
+}
+
+#[test]
+fn test_from_str_with_opts_depth_limit() {
+    use jasn::{de::Error, parser::ParseOptions};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Node {
+        children: Vec<Node>,
+    }
+
+    let opts = ParseOptions::new().with_max_depth(Some(4));
+    let shallow = "{children: [{children: []}]}";
+    let node: Node = jasn::de::from_str_with_opts(shallow, &opts).unwrap();
+    assert_eq!(node.children.len(), 1);
+
+    let deep = format!("{}{}", "{children: [".repeat(10), "]}".repeat(10));
+    let err = jasn::de::from_str_with_opts::<Node>(&deep, &opts).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ParseError(jasn::parser::Error::DepthLimitExceeded { limit: 4, .. })
+    ));
+    // Without the limit the same input deserializes fine
+    assert!(jasn::from_str::<Node>(&deep).is_ok());
+}
+
+#[test]
+fn test_deserialize_untagged_enum() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Point {
+        x: i64,
+        y: i64,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(untagged)]
+    enum Field {
+        Int(i64),
+        Text(String),
+        Point(Point),
+        List(Vec<Field>),
+        Bytes(#[serde(with = "serde_bytes")] Vec<u8>),
+        Missing(()),
+    }
+
+    let cases = [
+        ("42", Field::Int(42)),
+        ("'hi'", Field::Text("hi".to_string())),
+        ("{x: 1, y: -2}", Field::Point(Point { x: 1, y: -2 })),
+        (
+            "[1, 'a', {x: 0, y: 0}]",
+            Field::List(vec![
+                Field::Int(1),
+                Field::Text("a".to_string()),
+                Field::Point(Point { x: 0, y: 0 }),
+            ]),
+        ),
+        ("hex\"00ff\"", Field::Bytes(vec![0, 255])),
+        ("null", Field::Missing(())),
+        // Timestamps are presented as RFC 3339 strings
+        (
+            "ts\"2024-01-15T12:30:45Z\"",
+            Field::Text("2024-01-15T12:30:45Z".to_string()),
+        ),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(jasn::from_str::<Field>(input).unwrap(), expected, "{input}");
+    }
+
+    // No variant matches a map with the wrong fields
+    assert!(jasn::from_str::<Field>("{z: 1}").is_err());
+}
+
+#[test]
+fn test_deserialize_untagged_timestamp_variant() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(untagged)]
+    enum When {
+        At(#[serde(with = "time::serde::rfc3339")] time::OffsetDateTime),
+        Never(bool),
+    }
+
+    let when: When = jasn::from_str("ts\"2024-01-15T12:30:45.5+01:00\"").unwrap();
+    assert_eq!(
+        when,
+        When::At(time::macros::datetime!(2024-01-15 12:30:45.5 +01:00))
+    );
+    assert_eq!(jasn::from_str::<When>("false").unwrap(), When::Never(false));
+}
+
+#[test]
+fn test_from_str_empty_input() {
+    use jasn::{de::Error, parser::Error as ParseError};
+
+    for input in ["", "  /* nothing here */\n"] {
+        let err = jasn::from_str::<i64>(input).unwrap_err();
+        assert!(matches!(err, Error::ParseError(ParseError::EmptyDocument)));
+        assert_eq!(err.to_string(), "Parse error: Empty document");
+
+        // An empty document is not `None`
+        let err = jasn::from_str::<Option<i64>>(input).unwrap_err();
+        assert!(matches!(err, Error::ParseError(ParseError::EmptyDocument)));
+    }
+    assert_eq!(jasn::from_str::<Option<i64>>("null").unwrap(), None);
+}
@@ -27,8 +27,24 @@ fn test_all_valid_examples() {
         let content = fs::read_to_string(&example)
             .unwrap_or_else(|e| panic!("Failed to read {:?}: {}", example, e));
 
-        parse(&content)
+        let value = parse(&content)
             .unwrap_or_else(|e| panic!("Failed to parse valid example {:?}: {}", example, e));
+        let large = jasn::parser::parse_large(&content)
+            .unwrap_or_else(|e| panic!("parse_large failed on {:?}: {}", example, e));
+        assert!(
+            large.structural_eq(&value),
+            "parse_large differs on {:?}",
+            example
+        );
+
+        let crlf = content.replace("\r\n", "\n").replace('\n', "\r\n");
+        let crlf_value =
+            parse(&crlf).unwrap_or_else(|e| panic!("Failed to parse CRLF {:?}: {}", example, e));
+        assert!(
+            crlf_value.structural_eq(&value),
+            "CRLF version of {:?} parses differently",
+            example
+        );
     }
 }
 
@@ -103,3 +119,110 @@ fn test_comments() {
     let result = parse("/* multi-line\n       comment */ 42").unwrap();
     assert!(matches!(result, jasn::Value::Int(42)));
 }
+
+#[rstest::rstest]
+#[case::list(
+    "/*a*/[/*b*/1/*c*/,/*d*/2/*e*/,/*f*/]/*g*/",
+    jasn::Value::from([1, 2])
+)]
+#[case::list_no_trailing_comma("[ 1 /* c */ , 2 /* before close */ ]", jasn::Value::from([1, 2]))]
+#[case::empty_list("[ /* empty */ ]", jasn::Value::List(vec![]))]
+#[case::map(
+    "/*a*/{/*b*/x/*c*/:/*d*/1/*e*/,/*f*/'y'/*g*/:/*h*/[/**/]/*i*/,/*j*/}/*k*/",
+    jasn::Value::from([("x", jasn::Value::Int(1)), ("y", jasn::Value::List(vec![]))])
+)]
+#[case::empty_map("{ /* empty */ }", jasn::Value::Map(Default::default()))]
+#[case::multi_line(
+    "{\n  list: [\n    1, /* one */\n    /* two */ 2,\n    /* after trailing comma */\n  ],\n  /* before close */\n}",
+    jasn::Value::from([("list", [1, 2])])
+)]
+#[case::comment_like_content("[/* * / [ */ '/* not a comment */' /***/]", jasn::Value::from(["/* not a comment */"]))]
+fn test_comments_between_every_token(#[case] input: &str, #[case] expected: jasn::Value) {
+    assert_eq!(parse(input).unwrap(), expected);
+    assert_eq!(jasn::parser::parse_large(input).unwrap(), expected);
+}
+
+#[test]
+fn test_crlf_matches_lf() {
+    let lf = "/* header\n   comment */\n{\n  a: 1,\n  list: [\n    'x', /* note */\n  ],\n  data: b64\"\n    SGVs\n    bG8=\n  \",\n}\n";
+    let crlf = lf.replace('\n', "\r\n");
+    assert_eq!(parse(&crlf).unwrap(), parse(lf).unwrap());
+    assert_eq!(
+        jasn::parser::parse_large(&crlf).unwrap(),
+        parse(lf).unwrap()
+    );
+
+    let lf = "{\n  a: 1,\n  b: ]\n}";
+    let crlf = lf.replace('\n', "\r\n");
+    assert_eq!(
+        parse(&crlf).unwrap_err().line_col(),
+        parse(lf).unwrap_err().line_col()
+    );
+}
+
+#[test]
+fn test_trailing_comments_after_root_value() {
+    assert_eq!(parse("42 /* note */").unwrap(), jasn::Value::Int(42));
+    assert_eq!(parse("42/* a *//* b */\n").unwrap(), jasn::Value::Int(42));
+    assert_eq!(
+        parse("{a: 1} /* end */\n").unwrap(),
+        jasn::Value::from([("a", 1)])
+    );
+
+    // Trailing values are still rejected, comments or not
+    assert!(parse("42 43").is_err());
+    assert!(parse("42 /* note */ 43").is_err());
+    assert!(parse("{a: 1} {b: 2}").is_err());
+    // JASN has block comments only
+    assert!(parse("42 // note").is_err());
+}
+
+#[test]
+fn test_parse_bytes() {
+    use jasn::parser::parse_bytes;
+
+    let text = r#"{ name: "café", tags: [1, 2] }"#;
+    assert_eq!(parse_bytes(text.as_bytes()).unwrap(), parse(text).unwrap());
+
+    let mut with_bom = b"\xEF\xBB\xBF".to_vec();
+    with_bom.extend_from_slice(text.as_bytes());
+    assert_eq!(parse_bytes(&with_bom).unwrap(), parse(text).unwrap());
+}
+
+#[test]
+fn test_parse_prefix() {
+    use jasn::{Value, parser::parse_prefix};
+
+    assert_eq!(parse_prefix("42 rest").unwrap(), (Value::Int(42), 2));
+    assert_eq!(
+        parse_prefix("  /* c */ [1, 2]\n{}").unwrap(),
+        (Value::from([1, 2]), 16)
+    );
+    // Adjacent strings are separate values unless they are concatenated
+    assert_eq!(parse_prefix(r#""a" "b""#).unwrap(), (Value::from("a"), 3));
+
+    // Loop over a stream of values
+    let mut input = "1 {a: true} \"x\" 'y' null ";
+    let mut values = Vec::new();
+    while !input.trim().is_empty() {
+        let (value, end) = parse_prefix(input).unwrap();
+        values.push(value);
+        input = &input[end..];
+    }
+    assert_eq!(
+        values,
+        vec![
+            Value::Int(1),
+            Value::from([("a", true)]),
+            Value::from("x"),
+            Value::from("y"),
+            Value::Null
+        ]
+    );
+
+    assert!(matches!(
+        parse_prefix(" /* only */ "),
+        Err(jasn::parser::Error::EmptyDocument)
+    ));
+    assert!(parse_prefix("rest").is_err());
+}